@@ -0,0 +1,152 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use log::LevelFilter;
+
+use crate::game;
+
+/// The save file, from which the game is loaded and to which it is saved, if no other is given
+const DEFAULT_SAVE_FILE_NAME: &str = "k_best_kingdom.json";
+
+/// The options, which can be given on the command line. Without any options, the default save
+/// file is played in the terminal.
+#[derive(Debug, Parser)]
+#[command(version, about = "A kingdom simulation, which is played in the terminal")]
+pub struct Cli{
+    /// The name of the save file, from which the game is loaded and to which it is saved
+    #[arg(long, value_name = "FILE", default_value = DEFAULT_SAVE_FILE_NAME)]
+    pub save: String,
+
+    /// The directory, which contains the save files
+    #[arg(long, value_name = "DIR")]
+    pub saves_dir: Option<PathBuf>,
+
+    /// A directory, whose assets override the embedded ones
+    #[arg(long, value_name = "DIR")]
+    pub assets_dir: Option<PathBuf>,
+
+    /// Seeds the simulation anew, e.g. to play a new game or to reproduce a headless run
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// The speed, at which the game starts. At a speed of 2, two ticks pass every second.
+    #[arg(long, default_value_t = 1.0, value_parser = parse_speed)]
+    pub speed: f64,
+
+    /// The maximum number of frames per second, which are drawn because the game has changed.
+    /// Changes, which happen in between, are drawn together in the next frame.
+    #[arg(long, value_name = "FPS", default_value_t = 30, value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_fps: u32,
+
+    /// Runs the given number of ticks as fast as possible without the terminal-ui and prints the result
+    #[arg(long, value_name = "TICKS")]
+    pub headless: Option<u64>,
+
+    /// The file, to which the result of a headless run is written instead of stdout
+    #[arg(long, value_name = "FILE", requires = "headless")]
+    pub output: Option<PathBuf>,
+
+    /// Records every input event into the given replay file, e.g. to reproduce a bug
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["headless", "replay"])]
+    pub record: Option<PathBuf>,
+
+    /// Replays the input events of the given replay file instead of reading the keyboard. The game
+    /// starts in the state of the recording rather than from the save file.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["headless", "seed"])]
+    pub replay: Option<PathBuf>,
+
+    /// The file, to which the game writes its log. Without a log file, nothing is logged.
+    #[arg(long, value_name = "FILE")]
+    pub log_file: Option<PathBuf>,
+
+    /// The most detailed level, which is logged, e.g. error, warn, info, debug or trace
+    #[arg(long, value_name = "LEVEL", env = "RAILAN_LOG_LEVEL", default_value_t = LevelFilter::Info)]
+    pub log_level: LevelFilter,
+}
+
+/// Parses the game speed, which must lie between the slowest and the fastest speed of the game
+/// # Arguments
+/// * `speed` is the speed, which has been given on the command line
+fn parse_speed(speed: &str) -> Result<f64, String>{
+
+    let speed: f64 = speed.parse()
+        .map_err(|_| format!("'{speed}' is not a number"))?;
+
+    game::tick_interval(speed)?;
+
+    Ok(speed)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use clap::error::ErrorKind;
+    use super::*;
+
+    /// Parses the given arguments as if they had been given on the command line
+    /// # Arguments
+    /// * `args` are the arguments without the name of the program
+    fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(std::iter::once("railan").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn without_options_the_default_save_file_is_played() {
+
+        let cli = parse(&[]).unwrap();
+
+        assert_eq!(cli.save, DEFAULT_SAVE_FILE_NAME);
+        assert_eq!(cli.speed, 1.0);
+        assert!(cli.headless.is_none() && cli.record.is_none() && cli.replay.is_none() && cli.seed.is_none());
+    }
+
+    #[test]
+    fn speeds_outside_of_the_range_are_rejected() {
+
+        assert_eq!(parse(&["--speed", "2.5"]).unwrap().speed, 2.5);
+        assert_eq!(parse(&["--speed", &game::MIN_SPEED.to_string()]).unwrap().speed, game::MIN_SPEED);
+        assert_eq!(parse(&["--speed", &game::MAX_SPEED.to_string()]).unwrap().speed, game::MAX_SPEED);
+
+        for speed in ["0", "-1", "1e-300", "1e300", "NaN", "inf", "fast"] {
+            let error = parse(&[&format!("--speed={speed}")]).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::ValueValidation, "speed {speed}");
+        }
+    }
+
+    #[test]
+    fn invalid_values_are_rejected() {
+
+        assert_eq!(parse(&["--max-fps", "0"]).unwrap_err().kind(), ErrorKind::ValueValidation);
+        assert_eq!(parse(&["--headless", "many"]).unwrap_err().kind(), ErrorKind::ValueValidation);
+        assert_eq!(parse(&["--seed=-1"]).unwrap_err().kind(), ErrorKind::ValueValidation);
+        assert_eq!(parse(&["--log-level", "loud"]).unwrap_err().kind(), ErrorKind::ValueValidation);
+        assert_eq!(parse(&["--unknown"]).unwrap_err().kind(), ErrorKind::UnknownArgument);
+    }
+
+    #[test]
+    fn conflicting_options_are_rejected() {
+
+        let conflicts = [
+            ["--record", "a.replay", "--replay", "b.replay"],
+            ["--record", "a.replay", "--headless", "10"],
+            ["--replay", "a.replay", "--headless", "10"],
+            ["--replay", "a.replay", "--seed", "42"],
+        ];
+
+        for args in conflicts {
+            assert_eq!(parse(&args).unwrap_err().kind(), ErrorKind::ArgumentConflict, "{args:?}");
+        }
+
+        assert_eq!(parse(&["--output", "summary.json"]).unwrap_err().kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn compatible_options_are_accepted() {
+
+        let cli = parse(&["--headless", "10", "--seed", "42", "--output", "summary.json"]).unwrap();
+        assert_eq!((cli.headless, cli.seed), (Some(10), Some(42)));
+
+        let cli = parse(&["--record", "a.replay", "--seed", "42"]).unwrap();
+        assert_eq!(cli.record, Some(PathBuf::from("a.replay")));
+    }
+}
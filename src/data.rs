@@ -2,6 +2,7 @@ pub mod saves;
 pub mod kingdom;
 pub mod craft;
 pub mod village;
+pub mod neighbour;
 
 /// A trait, which is used to print game data to the main window in the terminal
 pub trait TerminalDisplay{
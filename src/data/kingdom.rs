@@ -1,26 +1,81 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_with::{serde_as};
 
 use super::craft::{Craft, CraftType};
+use super::neighbour::{Neighbour, Relation};
 use super::village::Village;
 
 /// A Kingdom is the main struct of the game. A player will access and manipulate its data.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 #[serde_as]
 pub struct Kingdom {
+    /// The name of the kingdom
+    #[serde(default)]
+    pub name: String,
+    /// The gold in the kingdom's treasury. It may become negative, if the kingdom is in debt.
+    #[serde(default)]
+    pub treasury: i32,
     /// A set of villages, which make up the kingdom
     pub villages: Vec<Village>,
-    /// A set of crafts, which are practices in the kingdom
+    /// A set of crafts, which are practices in the kingdom. They are kept in a sorted map so that
+    /// they are always displayed and saved in the same order.
     #[serde_as(as = "Vec<(DisplayFromStr, _)>")]
-    pub crafts: HashMap<CraftType, Vec<Craft>>,
+    pub crafts: BTreeMap<CraftType, Vec<Craft>>,
+    /// The foreign kingdoms, which border the kingdom
+    #[serde(default)]
+    pub neighbours: Vec<Neighbour>,
 }
 
 impl Kingdom {
     /// A first PoC- function so that the game loop has something to do :)
-    pub fn add_citizen(&mut self, citizen: i32) {
-        self.villages.get_mut(0).unwrap().num_citizen += citizen;
+    /// The number of citizens in a village will never drop below 0. If there is no such village,
+    /// nothing happens.
+    /// # Arguments
+    /// * `village` is the index of the village, which gains (or loses) the citizens
+    /// * `citizen` is the number of new citizens, which may be negative
+    pub fn add_citizen(&mut self, village: usize, citizen: i32) {
+        if let Some(village) = self.villages.get_mut(village) {
+            village.num_citizen = (village.num_citizen + citizen).max(0);
+        }
+    }
+
+    /// Renames a village. If there is no such village, nothing happens.
+    /// # Arguments
+    /// * `village` is the index of the village, which is renamed
+    /// * `name` is the new name of the village
+    pub fn rename_village(&mut self, village: usize, name: String) {
+        if let Some(village) = self.villages.get_mut(village) {
+            village.name = name;
+        }
+    }
+
+    /// Adds gold to or removes gold from the treasury
+    /// # Arguments
+    /// * `gold` is the amount of gold, which may be negative
+    pub fn add_treasury(&mut self, gold: i32) {
+        self.treasury += gold;
+    }
+
+    /// Raises the level of every craft of the given type. A level will never drop below 1.
+    /// # Arguments
+    /// * `craft_type` is the type of the crafts, which are raised
+    /// * `levels` is the number of levels, which may be negative
+    pub fn raise_craft_levels(&mut self, craft_type: CraftType, levels: i32) {
+        if let Some(crafts) = self.crafts.get_mut(&craft_type) {
+            crafts.iter_mut().for_each(|c| c.lvl = (c.lvl + levels).max(1));
+        }
+    }
+
+    /// Declares war on a neighbour and returns its name. If there is no such neighbour, nothing
+    /// happens and None is returned.
+    /// # Arguments
+    /// * `neighbour` is the index of the neighbour, on which war is declared
+    pub fn declare_war(&mut self, neighbour: usize) -> Option<&str> {
+        let neighbour = self.neighbours.get_mut(neighbour)?;
+        neighbour.relation = Relation::War;
+        Some(&neighbour.name)
     }
 }
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+use std::fmt::{Display, Formatter};
+
+use super::TerminalDisplay;
+
+/// A neighbour is a foreign kingdom bordering the player's kingdom
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Neighbour{
+    /// The name of the neighbour
+    pub name: String,
+    /// The diplomatic relation between the player's kingdom and the neighbour
+    pub relation: Relation,
+}
+
+impl TerminalDisplay for Neighbour{
+    fn display(&self) -> String {
+        format!("{}: {}\n", self.name, self.relation)
+    }
+}
+
+/// Relation contains every diplomatic relation, which the player's kingdom may have with a neighbour
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum Relation{
+    Peace,
+    War,
+}
+
+impl Display for Relation{
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+
+        match *self{
+            Relation::Peace => { write!(f,"Peace")}
+            Relation::War => { write!(f,"War")}
+        }
+    }
+}
@@ -1,21 +1,71 @@
 use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
-use super::kingdom::Kingdom;
+use crate::game::GameState;
 
 pub const SAVES_PATH : &str = "./saves";
 
-/// Reads a save file from the saves-folder and parses its JSON-content
+/// The directory, which contains the save files. It is set once at startup.
+static SAVES_DIR : OnceLock<PathBuf> = OnceLock::new();
+
+/// Sets the directory, which contains the save files. If no directory is given, SAVES_PATH is used.
+/// This function must be called at most once and before any save file is read or written.
+/// # Arguments
+/// * `dir` is the directory of the save files
+pub fn init_saves_dir(dir: Option<PathBuf>){
+    SAVES_DIR.set(dir.unwrap_or_else(|| PathBuf::from(SAVES_PATH)))
+        .expect("The saves directory should only be set once");
+}
+
+/// Returns the directory, which contains the save files
+fn saves_dir() -> &'static Path{
+    SAVES_DIR.get_or_init(|| PathBuf::from(SAVES_PATH))
+}
+
+/// Reads a save file from the saves-folder and parses its JSON-content into a GameState
 /// # Arguments
 /// * `file_name` is the name of the file, which should be read.
-pub fn load_save_file(file_name: &str) -> Kingdom{
+pub fn load_save_file(file_name: &str) -> Result<GameState, String>{
+
+    let path = saves_dir().join(file_name);
+
+    let data = fs::read_to_string(&path)
+        .map_err(|e| format!("Unable to read file '{}': {e}", path.display()))?;
+
+    let game_state = serde_json::from_str(&data)
+        .map_err(|e| format!("JSON in file '{}' was not well-formatted: {e}", path.display()))?;
+
+    log::info!(file:% = path.display(); "Loaded save file");
+    Ok(game_state)
+}
+
+/// Returns true, if a save file with the given name exists in the saves-folder
+/// # Arguments
+/// * `file_name` is the name of the file, which should be looked for
+pub fn save_file_exists(file_name: &str) -> bool{
+    saves_dir().join(file_name).is_file()
+}
+
+/// Writes a GameState as JSON into a save file in the saves-folder. An existing file will be
+/// overwritten. The content is written to a temporary file first, so that the previous save is
+/// not lost, if writing fails halfway.
+/// # Arguments
+/// * `file_name` is the name of the file, which should be written
+/// * `game_state` is the GameState, which is saved
+pub fn write_save_file(file_name: &str, game_state: &GameState) -> Result<(), String>{
 
-    let path = format!("{}/{file_name}",SAVES_PATH);
+    let path = saves_dir().join(file_name);
+    let temporary_path = path.with_extension("tmp");
 
-    let data = fs::read_to_string(path)
-        .expect(format!("Unable to read file '{SAVES_PATH}/{file_name}'").as_str());
+    let data = serde_json::to_string_pretty(game_state)
+        .map_err(|e| format!("Unable to serialise the game: {e}"))?;
 
-    let kingdom = serde_json::from_str(&data)
-        .expect(format!("JSON in file '{file_name}' was not well-formatted").as_str());
+    fs::create_dir_all(saves_dir())
+        .and_then(|_| fs::write(&temporary_path, data))
+        .and_then(|_| fs::rename(&temporary_path, &path))
+        .map_err(|e| format!("Unable to write file '{}': {e}", path.display()))?;
 
-    kingdom
-}
\ No newline at end of file
+    log::info!(file:% = path.display(), tick = game_state.ticks; "Wrote save file");
+    Ok(())
+}
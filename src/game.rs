@@ -1,18 +1,274 @@
+pub mod commands;
 pub mod game_loop;
+pub mod headless;
+pub mod messages;
+pub mod random_events;
+pub mod rng;
+pub mod statistics;
+pub mod systems;
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
 
 use super::data::kingdom::*;
+use messages::{Message, MessageCategory, MESSAGE_LOG_CAPACITY};
+use random_events::{PendingEvent, RandomEvent};
+use rng::SimulationRng;
+use statistics::StatisticsHistory;
+use systems::SYSTEMS;
+
+/// The span of time, which a single tick of the simulation covers
+pub const TICK_DURATION: Duration = Duration::from_secs(1);
+
+/// The slowest speed of the game, at which a tick passes every 1000 seconds
+pub const MIN_SPEED: f64 = 0.001;
+
+/// The fastest speed of the game, at which 1000 ticks pass every second
+pub const MAX_SPEED: f64 = 1000.0;
+
+/// Returns the real time between two ticks at the given speed. Speeds outside of MIN_SPEED and
+/// MAX_SPEED are rejected, since the game-loop would either hardly ever wake up or never sleep at all
+/// and since the time between two ticks can't even be represented for some of them.
+/// # Arguments
+/// * `speed` is the speed of the game, e.g. at a speed of 2 two ticks pass every second
+pub fn tick_interval(speed: f64) -> Result<Duration, String>{
+
+    if (MIN_SPEED..=MAX_SPEED).contains(&speed) {
+        Ok(TICK_DURATION.div_f64(speed))
+    } else {
+        Err(format!("The speed must be a number between {MIN_SPEED} and {MAX_SPEED}"))
+    }
+}
 
-/// A struct to hold general data about the running game. Currently it only holds the kingdom,
-/// but will be extended by other variables in the future.
+/// A struct to hold general data about the running game. Currently it holds the kingdom, the
+/// random number generator of the simulation, a random event awaiting the player's reaction, a
+/// log of messages and a history of statistics, but will be extended by other variables in the future.
+/// It is written to and read from save files, wherein the kingdom's data is stored at the top level
+/// of the file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
 pub struct GameState{
     /// The current player's kingdom
+    #[serde(flatten)]
     pub kingdom : Kingdom,
+    /// The number of ticks, which the simulation has run so far
+    #[serde(default)]
+    pub ticks : u64,
+    /// The only source of randomness for the simulation. Save files without a generator will
+    /// start from the default seed.
+    #[serde(default)]
+    pub rng : SimulationRng,
+    /// A random event, which has occurred and awaits the player's choice
+    #[serde(default)]
+    pub pending_event : Option<PendingEvent>,
+    /// The most recent messages, the oldest one first. The log is shared between the snapshots of
+    /// the game state and only copied, when a message is posted.
+    #[serde(default)]
+    pub message_log : Arc<VecDeque<Message>>,
+    /// The history of the kingdom's statistics, which is saved down-sampled
+    #[serde(default)]
+    pub statistics : StatisticsHistory,
+    /// Messages, which have been posted, but not yet sent to the user-interface
+    #[serde(skip)]
+    unsent_messages : Vec<Message>,
+    /// Every random event, which may occur during the game. It is read from the assets instead of
+    /// the save file.
+    #[serde(skip)]
+    pub event_catalogue : Arc<Vec<RandomEvent>>,
+    /// The time, which has passed, but is too short for another tick
+    #[serde(skip)]
+    unsimulated_time : Duration,
+    /// The number of changes since the game has been loaded. It allows the user-interface to tell,
+    /// whether the game state has changed since it has been drawn.
+    #[serde(skip)]
+    revision : u64,
 }
 
 impl GameState{
-    pub fn new(kingdom : Kingdom) -> GameState {
-        GameState{
-            kingdom
+
+    /// Advances the simulation by the time, which has passed. For every full tick within that time,
+    /// every system runs once in order. The remaining time is kept for the next call, so that no
+    /// tick is lost, if the calls are late. Returns the number of ticks, which have been run.
+    /// # Arguments
+    /// * `dt` is the time, which has passed since the last call
+    pub fn tick(&mut self, dt: Duration) -> u64{
+
+        self.unsimulated_time += dt;
+
+        let mut ticks = 0;
+
+        while self.unsimulated_time >= TICK_DURATION {
+
+            self.unsimulated_time -= TICK_DURATION;
+            self.ticks += 1;
+            log::trace!(tick = self.ticks; "Tick");
+
+            for system in SYSTEMS {
+                system(self);
+            }
+
+            self.mark_changed();
+
+            ticks += 1;
         }
+
+        ticks
+    }
+
+    /// Returns the number of changes since the game has been loaded
+    pub fn get_revision(&self) -> u64{
+        self.revision
+    }
+
+    /// Increases the revision, which must be done after every change of the game state
+    pub fn mark_changed(&mut self){
+        self.revision += 1;
     }
-}
\ No newline at end of file
+
+    /// Posts a message for the current tick. It is added to the message log and will be sent to
+    /// the user-interface with the next call of `take_unsent_messages`.
+    /// # Arguments
+    /// * `category` is the category of the message
+    /// * `text` is the text of the message
+    pub fn post_message(&mut self, category: MessageCategory, text: String){
+
+        let message = Message { tick: self.ticks, category, text };
+        let message_log = Arc::make_mut(&mut self.message_log);
+
+        if message_log.len() == MESSAGE_LOG_CAPACITY {
+            message_log.pop_front();
+        }
+
+        message_log.push_back(message.clone());
+        self.unsent_messages.push(message);
+    }
+
+    /// Returns every message, which has been posted since the last call of this method
+    pub fn take_unsent_messages(&mut self) -> Vec<Message>{
+        std::mem::take(&mut self.unsent_messages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::data::saves::load_save_file;
+    use crate::menu::assets::read_asset;
+    use random_events::{load_event_catalogue, Choice, Condition, RandomEvent};
+
+    /// Returns the game state of the save file, which is seeded with the given seed and knows every
+    /// random event
+    /// # Arguments
+    /// * `seed` is the seed of the simulation
+    fn seeded_game_state(seed: u64) -> GameState {
+
+        let mut game_state = load_save_file("k_best_kingdom.json").unwrap();
+        game_state.rng = SimulationRng::new(seed);
+        game_state.event_catalogue = Arc::new(load_event_catalogue(&read_asset("events.json").unwrap()).unwrap());
+        game_state
+    }
+
+    #[test]
+    fn the_same_seed_leads_to_the_same_game() {
+
+        let mut first = seeded_game_state(42);
+        let mut second = seeded_game_state(42);
+
+        for _ in 0..500 {
+            for game_state in [&mut first, &mut second] {
+
+                game_state.tick(TICK_DURATION);
+
+                // Resolve every event, so that further events may occur
+                if game_state.pending_event.is_some() {
+                    random_events::resolve_pending_event(game_state, 0);
+                }
+            }
+        }
+
+        // The random events must have drawn from the generator for the test to be meaningful
+        assert_eq!(first.ticks, 500);
+        assert!(!first.message_log.is_empty());
+        assert_eq!(serde_json::to_string(&first).unwrap(), serde_json::to_string(&second).unwrap());
+    }
+
+    #[test]
+    fn the_systems_run_in_order() {
+
+        let mut game_state = seeded_game_state(42);
+
+        // In a single village, the new citizen moves into that very village
+        game_state.kingdom.villages.truncate(1);
+        game_state.kingdom.crafts.values_mut().flatten().for_each(|craft| craft.lvl = 10);
+        game_state.ticks = 29;
+
+        let num_citizen = game_state.kingdom.villages[0].num_citizen;
+        let treasury = game_state.kingdom.treasury;
+
+        // The event can only occur, if the population has grown before
+        game_state.event_catalogue = Arc::new(vec![RandomEvent {
+            name: String::from("Growth"),
+            category: messages::MessageCategory::Economy,
+            description: String::from("{village} has grown"),
+            probability: 1.0,
+            conditions: vec![Condition::MinCitizens(num_citizen + 1)],
+            choices: vec![Choice { label: String::from("Celebrate"), consequences: vec![] }],
+        }]);
+
+        assert_eq!(game_state.tick(TICK_DURATION), 1);
+
+        // The crafts have sold their goods in the 30th tick
+        assert!(game_state.kingdom.treasury > treasury);
+        assert!(game_state.pending_event.is_some());
+
+        // The statistics are recorded after the population has grown and the crafts have produced
+        let sample = game_state.statistics.latest().unwrap();
+        assert_eq!(sample.tick, 30);
+        assert_eq!(sample.citizens, vec![num_citizen + 1]);
+        assert_eq!(sample.treasury, game_state.kingdom.treasury);
+    }
+
+    #[test]
+    fn time_shorter_than_a_tick_is_accumulated() {
+
+        let mut game_state = seeded_game_state(42);
+        let revision = game_state.get_revision();
+        let num_samples = game_state.statistics.len();
+
+        assert_eq!(game_state.tick(TICK_DURATION / 2), 0);
+        assert_eq!(game_state.tick(Duration::ZERO), 0);
+        assert_eq!(game_state.get_revision(), revision);
+
+        // Both halves add up to a full tick
+        assert_eq!(game_state.tick(TICK_DURATION * 6 / 10), 1);
+        assert_eq!(game_state.get_revision(), revision + 1);
+
+        // The remaining tenth adds up with the next call
+        assert_eq!(game_state.tick(TICK_DURATION * 29 / 10), 3);
+        assert_eq!(game_state.get_revision(), revision + 4);
+        assert_eq!(game_state.statistics.len(), num_samples + 4);
+    }
+
+    #[test]
+    fn the_message_log_keeps_the_newest_messages() {
+
+        let mut game_state = seeded_game_state(42);
+        game_state.message_log = Default::default();
+
+        for i in 0..MESSAGE_LOG_CAPACITY + 100 {
+            game_state.post_message(MessageCategory::Economy, format!("Message {i}"));
+        }
+
+        assert_eq!(game_state.message_log.len(), MESSAGE_LOG_CAPACITY);
+        assert_eq!(game_state.message_log.front().unwrap().text, "Message 100");
+        assert_eq!(game_state.message_log.back().unwrap().text, format!("Message {}", MESSAGE_LOG_CAPACITY + 99));
+
+        // Every message is sent to the user-interface exactly once, even if it has left the log
+        assert_eq!(game_state.take_unsent_messages().len(), MESSAGE_LOG_CAPACITY + 100);
+        assert!(game_state.take_unsent_messages().is_empty());
+    }
+}
@@ -0,0 +1,148 @@
+use flume::{bounded, Sender};
+
+use super::{GameState, TICK_DURATION};
+use super::messages::MessageCategory;
+use super::random_events;
+
+/// A change of the game state, which the user-interface asks the game-loop for. As only the
+/// game-loop mutates the game state, the user-interface never blocks the simulation.
+#[derive(Debug)]
+pub enum Command {
+    /// Resolves the pending random event with the choice of the given index
+    ResolveEvent(usize),
+    /// Declares war on the neighbour of the given index
+    DeclareWar(usize),
+    /// Renames the village of the given index
+    RenameVillage(usize, String),
+    /// Advances the game by a single tick, e.g. while a replay drives the game
+    Tick,
+}
+
+/// A command together with a channel, through which the game-loop reports, that it has been applied
+pub struct CommandRequest {
+    /// The requested command
+    pub command: Command,
+    /// The channel, to which the game-loop sends a token, once the command has been applied
+    pub applied_tx: Sender<()>,
+}
+
+/// Sends commands to the game-loop
+#[derive(Clone)]
+pub struct CommandSender {
+    /// The channel to the game-loop
+    command_tx: Sender<CommandRequest>,
+}
+
+impl CommandSender {
+
+    /// Creates a sender for commands
+    /// # Arguments
+    /// * `command_tx` is the channel to the game-loop
+    pub fn new(command_tx: Sender<CommandRequest>) -> CommandSender {
+        CommandSender { command_tx }
+    }
+
+    /// Sends a command to the game-loop and waits, until it has been applied. Afterwards, the
+    /// published snapshot of the game state contains the change. If the game-loop has already
+    /// stopped, the command is dropped.
+    /// # Arguments
+    /// * `command` is the command, which is applied
+    pub fn execute(&self, command: Command) {
+
+        let (applied_tx, applied_rx) = bounded(1);
+
+        if self.command_tx.send(CommandRequest { command, applied_tx }).is_ok() {
+            let _ = applied_rx.recv();
+        }
+    }
+}
+
+/// Applies a command to the game state and increases its revision. Returns an error, if the command
+/// refers to something, which doesn't exist (anymore), e.g. a choice of an event, which has been
+/// resolved in the meantime. The game state is left unchanged in that case.
+/// # Arguments
+/// * `game_state` is the game state, which is changed
+/// * `command` is the command, which is applied
+pub fn apply(game_state: &mut GameState, command: Command) -> Result<(), String> {
+
+    log::debug!(command:? = command; "Applying command");
+
+    match command {
+        Command::ResolveEvent(choice) => {
+            if !matches!(&game_state.pending_event, Some(pending) if choice < pending.event.choices.len()) {
+                return Err(format!("There is no pending event with a choice {}", choice + 1));
+            }
+            random_events::resolve_pending_event(game_state, choice);
+            game_state.mark_changed();
+        }
+        Command::DeclareWar(neighbour) => {
+            let name = game_state.kingdom.declare_war(neighbour).map(String::from)
+                .ok_or_else(|| format!("There is no neighbour {}", neighbour + 1))?;
+            game_state.post_message(MessageCategory::Diplomacy, format!("You declared war on {name}."));
+            game_state.mark_changed();
+        }
+        Command::RenameVillage(village, name) => {
+            if village >= game_state.kingdom.villages.len() {
+                return Err(format!("There is no village {}", village + 1));
+            }
+            game_state.kingdom.rename_village(village, name);
+            game_state.mark_changed();
+        }
+        // Every tick increases the revision by itself
+        Command::Tick => {
+            game_state.tick(TICK_DURATION);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::data::saves::load_save_file;
+
+    /// Applies the command and returns the increase of the revision, or None, if the command has
+    /// been rejected
+    /// # Arguments
+    /// * `game_state` is the game state, which is changed
+    /// * `command` is the command, which is applied
+    fn revision_increase(game_state: &mut GameState, command: Command) -> Option<u64> {
+
+        let revision = game_state.get_revision();
+        let result = apply(game_state, command);
+        let increase = game_state.get_revision() - revision;
+
+        match result {
+            Ok(()) => Some(increase),
+            Err(_) => {
+                assert_eq!(increase, 0, "a rejected command must not change the revision");
+                None
+            }
+        }
+    }
+
+    #[test]
+    fn every_applied_command_increases_the_revision() {
+
+        let mut game_state = load_save_file("k_best_kingdom.json").unwrap();
+
+        assert_eq!(revision_increase(&mut game_state, Command::RenameVillage(0, String::from("Füchtorf"))), Some(1));
+        assert_eq!(revision_increase(&mut game_state, Command::DeclareWar(0)), Some(1));
+        assert_eq!(revision_increase(&mut game_state, Command::Tick), Some(1));
+    }
+
+    #[test]
+    fn rejected_commands_leave_the_revision_unchanged() {
+
+        let mut game_state = load_save_file("k_best_kingdom.json").unwrap();
+        game_state.pending_event = None;
+        let num_villages = game_state.kingdom.villages.len();
+        let num_neighbours = game_state.kingdom.neighbours.len();
+
+        assert_eq!(revision_increase(&mut game_state, Command::ResolveEvent(0)), None);
+        assert_eq!(revision_increase(&mut game_state, Command::RenameVillage(num_villages, String::from("Nowhere"))), None);
+        assert_eq!(revision_increase(&mut game_state, Command::DeclareWar(num_neighbours)), None);
+    }
+}
@@ -1,22 +1,266 @@
-use std::thread;
-use std::time::Duration;
-use std::sync::{Arc, RwLock};
-use crossbeam::channel::{Receiver};
-use super::GameState;
+use std::time::Instant;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use arc_swap::ArcSwap;
+use flume::{Receiver, Selector, Sender};
+use super::{GameState, TICK_DURATION};
+use super::commands::{self, CommandRequest};
+use super::messages::Message;
+use crate::menu::UIError;
 
-pub fn game_loop(game_state: Arc<RwLock<GameState>>, quit_rx: Receiver<String>){
+/// A notification from the game-loop to the user-interface, which wakes it up to redraw
+pub enum Notification {
+    /// A message, which has been posted during a tick
+    Message(Message),
+    /// The game state has changed and has reached the given revision
+    StateChanged(u64),
+}
+
+/// The reason, why the game-loop has woken up
+enum Wakeup {
+    /// The user-interface has requested a command
+    Command(CommandRequest),
+    /// The time for the next tick has come
+    Tick,
+    /// A quit-token has been received or the channels have been closed
+    Quit,
+}
+
+/// The channels, through which the game-loop communicates with the other threads
+pub struct GameLoopChannels {
+    /// A receiver for the channel, which will receive a quit-token
+    pub quit_rx: Receiver<String>,
+    /// A receiver for the channel, which will receive the commands of the user-interface
+    pub command_rx: Receiver<CommandRequest>,
+    /// A transmitter for the channel, which will communicate new messages and changes
+    pub notification_tx: Sender<Notification>,
+    /// A transmitter for the channel, which will communicate commands, which can't be applied
+    pub error_tx: Sender<UIError>,
+}
+
+/// Runs the simulation, advancing the game state once per tick until a quit-token is received.
+/// The game state is advanced by the time, which has actually passed multiplied by the game's
+/// speed, so that a late wake-up doesn't slow down the simulation. In between, the commands of the
+/// user-interface are applied.
+/// The game-loop is the only thread, which mutates the game state. After every change, it publishes
+/// an immutable snapshot, which the user-interface reads without ever blocking the simulation.
+/// Every message, which is posted, is sent to the user-interface afterwards, followed by a
+/// notification, that the game state has changed.
+/// # Arguments
+/// * `game_state` is the game state, which is mutated every tick
+/// * `snapshot` is the snapshot of the game state, which is replaced after every change
+/// * `speed` is the speed of the game, e.g. at a speed of 2 two ticks pass every second
+/// * `paused` is set, while the game must not be advanced, e.g. because a replay drives the game
+/// * `channels` are the channels to the other threads
+pub fn game_loop(mut game_state: GameState, snapshot: Arc<ArcSwap<GameState>>, speed: f64, paused: Arc<AtomicBool>, channels: GameLoopChannels){
+
+    log::info!(speed; "Game loop started");
+
+    let GameLoopChannels { quit_rx, command_rx, notification_tx, error_tx } = channels;
+
+    let tick_interval = TICK_DURATION.div_f64(speed);
+    let mut last_tick = Instant::now();
+    let mut next_tick = last_tick + tick_interval;
 
     loop{
 
-        thread::sleep(Duration::new (1,0));
+        // Wait for the next tick, but apply every command, which arrives in the meantime. A
+        // quit-token ends the wait immediately, no matter how long a tick lasts at a slow speed.
+        let wakeup = Selector::new()
+            .recv(&command_rx, |request| request.map_or(Wakeup::Quit, Wakeup::Command))
+            .recv(&quit_rx, |_| Wakeup::Quit)
+            .wait_deadline(next_tick)
+            .unwrap_or(Wakeup::Tick);
+
+        match wakeup {
+            Wakeup::Quit => break,
+            Wakeup::Command(CommandRequest { command, applied_tx }) => {
+
+                let revision = game_state.get_revision();
+
+                // A rejected command is reported, but the game goes on
+                if let Err(e) = commands::apply(&mut game_state, command) {
+                    log::warn!(error:% = e; "Command rejected");
+                    let _ = error_tx.try_send(UIError::Command(e));
+                }
+
+                // A snapshot is only published, if the command has actually changed the game state
+                if game_state.get_revision() != revision {
+                    publish(&mut game_state, &snapshot, &notification_tx);
+                }
+
+                // The user-interface may have stopped waiting in the meantime
+                let _ = applied_tx.send(());
+            }
+            Wakeup::Tick => {
+
+                next_tick = Instant::now() + tick_interval;
+
+                // The time, which passes during a pause, is skipped
+                if paused.load(Ordering::Acquire) {
+                    last_tick = Instant::now();
+                    continue;
+                }
 
-        if let Ok(msg) = quit_rx.try_recv() {
-            if msg.eq("quit"){
-                break;
+                let now = Instant::now();
+                let ticks = game_state.tick((now - last_tick).mul_f64(speed));
+                last_tick = now;
+
+                if ticks > 0 {
+                    publish(&mut game_state, &snapshot, &notification_tx);
+                }
             }
         }
+    }
+
+    log::info!(tick = game_state.ticks; "Game loop stopped");
+}
+
+/// Publishes a snapshot of the changed game state and notifies the user-interface about every
+/// message, which has been posted since the last snapshot, and about the change itself
+/// # Arguments
+/// * `game_state` is the game state, which has changed
+/// * `snapshot` is the snapshot, which is replaced
+/// * `notification_tx` a transmitter for the channel, which will communicate new messages and changes
+fn publish(game_state: &mut GameState, snapshot: &ArcSwap<GameState>, notification_tx: &Sender<Notification>){
+
+    let messages = game_state.take_unsent_messages();
+
+    // The message log and the statistics history are shared with the snapshot rather than copied
+    snapshot.store(Arc::new(game_state.clone()));
+
+    // A full channel only means that the user-interface lags behind. As every message is
+    // still kept in the message log and the user-interface redraws anyway, they may be dropped safely.
+    for message in messages {
+        let _ = notification_tx.try_send(Notification::Message(message));
+    }
+
+    let _ = notification_tx.try_send(Notification::StateChanged(game_state.get_revision()));
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::thread::{self, JoinHandle};
+    use std::time::Duration;
+    use flume::bounded;
+    use super::*;
+    use crate::game::{MAX_SPEED, MIN_SPEED};
+    use crate::data::saves::load_save_file;
+    use crate::game::commands::{Command, CommandSender};
+
+    /// The threads and channels of a game-loop, which runs during a test
+    struct RunningGameLoop {
+        handle: JoinHandle<()>,
+        snapshot: Arc<ArcSwap<GameState>>,
+        quit_tx: Sender<String>,
+        commands: CommandSender,
+        notification_rx: Receiver<Notification>,
+        error_rx: Receiver<UIError>,
+        // Keeps the command channel open, like the thread communication does
+        _command_tx: Sender<CommandRequest>,
+    }
+
+    /// Starts a game-loop with the game state of the save file in its own thread
+    /// # Arguments
+    /// * `speed` is the speed of the game
+    fn start_game_loop(speed: f64) -> RunningGameLoop {
+
+        let game_state = load_save_file("k_best_kingdom.json").unwrap();
+        let snapshot = Arc::new(ArcSwap::from_pointee(game_state.clone()));
+        let (quit_tx, quit_rx) = bounded(1);
+        let (command_tx, command_rx) = bounded(1);
+        let (notification_tx, notification_rx) = bounded(100);
+        let (error_tx, error_rx) = bounded(16);
+
+        let channels = GameLoopChannels { quit_rx, command_rx, notification_tx, error_tx };
+        let loop_snapshot = Arc::clone(&snapshot);
+        let handle = thread::spawn(move || {
+            game_loop(game_state, loop_snapshot, speed, Arc::new(AtomicBool::new(false)), channels)
+        });
+
+        RunningGameLoop {
+            handle,
+            snapshot,
+            quit_tx,
+            commands: CommandSender::new(command_tx.clone()),
+            notification_rx,
+            error_rx,
+            _command_tx: command_tx,
+        }
+    }
+
+    #[test]
+    fn a_quit_token_stops_the_game_loop_before_the_next_tick() {
+
+        // At this speed, the next tick would be due in 1000 seconds
+        let game_loop = start_game_loop(MIN_SPEED);
+        let started = Instant::now();
+
+        game_loop.quit_tx.send(String::from("quit")).unwrap();
+        game_loop.handle.join().unwrap();
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(game_loop.snapshot.load().ticks, 0);
+    }
+
+    #[test]
+    fn rejected_commands_are_reported_through_the_error_channel() {
+
+        let game_loop = start_game_loop(MIN_SPEED);
+        let revision = game_loop.snapshot.load().get_revision();
+        let missing_village = game_loop.snapshot.load().kingdom.villages.len();
+
+        game_loop.commands.execute(Command::RenameVillage(missing_village, String::from("Nowhere")));
+
+        assert!(matches!(game_loop.error_rx.try_recv(), Ok(UIError::Command(_))));
+        assert_eq!(game_loop.snapshot.load().get_revision(), revision);
+        assert!(game_loop.notification_rx.is_empty());
+
+        game_loop.quit_tx.send(String::from("quit")).unwrap();
+        game_loop.handle.join().unwrap();
+    }
+
+    #[test]
+    fn a_snapshot_is_published_after_every_applied_command() {
+
+        let game_loop = start_game_loop(MIN_SPEED);
+        let revision = game_loop.snapshot.load().get_revision();
+
+        game_loop.commands.execute(Command::DeclareWar(0));
+
+        // The command has been applied, before its sender stops waiting
+        let snapshot = game_loop.snapshot.load_full();
+        assert!(snapshot.get_revision() > revision);
+        assert!(snapshot.message_log.back().unwrap().text.starts_with("You declared war on"));
+
+        // The message is sent before the notification about the change
+        assert!(matches!(game_loop.notification_rx.try_recv(), Ok(Notification::Message(message)) if message.text.starts_with("You declared war on")));
+        assert!(matches!(game_loop.notification_rx.try_recv(), Ok(Notification::StateChanged(r)) if r == snapshot.get_revision()));
+
+        game_loop.quit_tx.send(String::from("quit")).unwrap();
+        game_loop.handle.join().unwrap();
+    }
+
+    #[test]
+    fn a_snapshot_is_published_after_every_tick() {
+
+        let game_loop = start_game_loop(MAX_SPEED);
+
+        // Messages of the tick may be sent before the notification about the change
+        let revision = loop {
+            match game_loop.notification_rx.recv_timeout(Duration::from_secs(5)).unwrap() {
+                Notification::StateChanged(revision) => break revision,
+                Notification::Message(_) => continue,
+            }
+        };
+
+        // The snapshot contains the tick, before the user-interface is notified about it
+        let snapshot = game_loop.snapshot.load_full();
+        assert!(snapshot.get_revision() >= revision);
+        assert!(snapshot.ticks > 0);
 
-        let mut write_guard = game_state.write().unwrap();
-        write_guard.kingdom.add_citizen(1);
+        game_loop.quit_tx.send(String::from("quit")).unwrap();
+        game_loop.handle.join().unwrap();
     }
-}
\ No newline at end of file
+}
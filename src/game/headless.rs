@@ -0,0 +1,182 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::{GameState, TICK_DURATION};
+use super::messages::MessageCategory;
+use super::random_events;
+
+/// The index of the choice, with which every random event is resolved during a headless run
+const EVENT_CHOICE: usize = 0;
+
+/// The policy for random events during a headless run, which is stated in the summary
+const EVENT_POLICY: &str = "Every random event is resolved with its first choice";
+
+/// Summary statistics of a headless run, which allow to check the balance of the economy
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Summary {
+    /// The number of ticks, which have been run
+    pub ticks_run: u64,
+    /// The tick of the game state after the run
+    pub final_tick: u64,
+    /// The gold in the treasury before the run
+    pub treasury_before: i32,
+    /// The gold in the treasury after the run
+    pub treasury_after: i32,
+    /// The total number of citizens across all villages of the kingdom before the run
+    pub citizens_before: i64,
+    /// The total number of citizens across all villages of the kingdom after the run
+    pub citizens_after: i64,
+    /// The number of random events, which have occurred during the run
+    pub random_events: u64,
+    /// How the random events have been resolved, as there is no player to choose
+    pub event_policy: &'static str,
+    /// The number of messages about the economy, which have been posted during the run
+    pub economy_messages: u64,
+    /// The number of messages about diplomacy, which have been posted during the run
+    pub diplomacy_messages: u64,
+    /// The number of military messages, which have been posted during the run
+    pub military_messages: u64,
+}
+
+/// The result of a headless run, which is written as JSON
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct Report<'a> {
+    /// The summary statistics of the run
+    summary: &'a Summary,
+    /// The game state after the run
+    state: &'a GameState,
+}
+
+/// Advances the simulation by the given number of ticks as fast as possible, without any terminal.
+/// As there is no player to react to random events, every event is resolved with its first choice,
+/// which is stated in the summary as well.
+/// # Arguments
+/// * `game_state` is the game state, which is advanced
+/// * `ticks` is the number of ticks, which are run
+pub fn run_headless(game_state: &mut GameState, ticks: u64) -> Summary {
+
+    let mut summary = Summary {
+        ticks_run: ticks,
+        treasury_before: game_state.kingdom.treasury,
+        citizens_before: count_citizens(game_state),
+        event_policy: EVENT_POLICY,
+        ..Summary::default()
+    };
+
+    // An event, which has been pending in the save file, would block every other event
+    random_events::resolve_pending_event(game_state, EVENT_CHOICE);
+
+    for _ in 0..ticks {
+
+        game_state.tick(TICK_DURATION);
+
+        if game_state.pending_event.is_some() {
+            summary.random_events += 1;
+            random_events::resolve_pending_event(game_state, EVENT_CHOICE);
+        }
+
+        // Nobody receives the messages, but they are counted, so that they don't pile up
+        for message in game_state.take_unsent_messages() {
+            match message.category {
+                MessageCategory::Economy => summary.economy_messages += 1,
+                MessageCategory::Diplomacy => summary.diplomacy_messages += 1,
+                MessageCategory::Military => summary.military_messages += 1,
+            }
+        }
+    }
+
+    summary.final_tick = game_state.ticks;
+    summary.treasury_after = game_state.kingdom.treasury;
+    summary.citizens_after = count_citizens(game_state);
+
+    summary
+}
+
+/// Writes the summary and the game state after a headless run as JSON, either to a file or to
+/// stdout.
+/// # Arguments
+/// * `summary` is the summary of the run
+/// * `game_state` is the game state after the run
+/// * `output` is the file, to which the report is written. If it is None, stdout is used.
+pub fn write_report(summary: &Summary, game_state: &GameState, output: Option<&Path>) -> Result<(), String> {
+
+    let json = serde_json::to_string_pretty(&Report { summary, state: game_state })
+        .map_err(|e| format!("Unable to serialize the report: {e}"))?;
+
+    match output {
+        Some(path) => fs::write(path, json)
+            .map_err(|e| format!("Unable to write the report to '{}': {e}", path.display())),
+        None => writeln!(io::stdout().lock(), "{json}")
+            .map_err(|e| format!("Unable to write the report to stdout: {e}")),
+    }
+}
+
+/// Returns the total number of citizens across all villages of the kingdom
+/// # Arguments
+/// * `game_state` is the game state, whose villages are counted
+fn count_citizens(game_state: &GameState) -> i64 {
+    game_state.kingdom.villages.iter()
+        .map(|v| v.num_citizen as i64)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::sync::Arc;
+    use super::*;
+    use crate::data::saves::load_save_file;
+    use crate::game::rng::SimulationRng;
+    use crate::menu::assets::read_asset;
+    use random_events::load_event_catalogue;
+
+    /// Returns the game state of the save file, which is seeded with the given seed and knows every
+    /// random event
+    /// # Arguments
+    /// * `seed` is the seed of the simulation
+    fn seeded_game_state(seed: u64) -> GameState {
+
+        let mut game_state = load_save_file("k_best_kingdom.json").unwrap();
+        game_state.rng = SimulationRng::new(seed);
+        game_state.event_catalogue = Arc::new(load_event_catalogue(&read_asset("events.json").unwrap()).unwrap());
+        game_state
+    }
+
+    #[test]
+    fn the_same_seed_leads_to_the_same_summary() {
+
+        let mut first = seeded_game_state(42);
+        let mut second = seeded_game_state(42);
+
+        let first_summary = run_headless(&mut first, 500);
+        let second_summary = run_headless(&mut second, 500);
+
+        assert_eq!(serde_json::to_string(&first_summary).unwrap(), serde_json::to_string(&second_summary).unwrap());
+        assert_eq!(serde_json::to_string(&first).unwrap(), serde_json::to_string(&second).unwrap());
+
+        // The random events must have occurred for the test to be meaningful
+        assert!(first_summary.random_events > 0);
+        assert!(first.pending_event.is_none());
+    }
+
+    #[test]
+    fn the_summary_covers_the_whole_run() {
+
+        let mut game_state = seeded_game_state(7);
+        let ticks_before = game_state.ticks;
+
+        let summary = run_headless(&mut game_state, 100);
+
+        assert_eq!(summary.ticks_run, 100);
+        assert_eq!(summary.final_tick, ticks_before + 100);
+        assert_eq!(summary.treasury_after, game_state.kingdom.treasury);
+        assert_eq!(summary.citizens_after, game_state.kingdom.villages.iter().map(|v| v.num_citizen as i64).sum::<i64>());
+        assert_eq!(summary.event_policy, EVENT_POLICY);
+        assert!(game_state.take_unsent_messages().is_empty());
+    }
+}
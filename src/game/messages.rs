@@ -0,0 +1,46 @@
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of messages, which are kept in the message log. Older messages are dropped.
+pub const MESSAGE_LOG_CAPACITY: usize = 500;
+
+/// The category of a message, which allows the player to filter the message log
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum MessageCategory {
+    Economy,
+    Diplomacy,
+    Military,
+}
+
+impl Display for MessageCategory {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+
+        match *self {
+            MessageCategory::Economy => { write!(f, "Economy") }
+            MessageCategory::Diplomacy => { write!(f, "Diplomacy") }
+            MessageCategory::Military => { write!(f, "Military") }
+        }
+    }
+}
+
+/// A message about something, which has happened in the game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Message {
+    /// The tick, in which the message was posted
+    pub tick: u64,
+    /// The category of the message
+    pub category: MessageCategory,
+    /// The text of the message
+    pub text: String,
+}
+
+impl Display for Message {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Day {} [{}] {}", self.tick, self.category, self.text)
+    }
+}
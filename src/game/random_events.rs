@@ -0,0 +1,250 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::craft::CraftType;
+use crate::data::kingdom::Kingdom;
+use super::GameState;
+use super::messages::MessageCategory;
+
+/// A random event, which may occur in one of the kingdom's villages. Random events are not
+/// hard-coded, but read from the asset 'events.json'. Whenever an event occurs, the player must
+/// choose how to react to it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct RandomEvent {
+    /// The name of the event, which is also used as title of the event screen
+    pub name: String,
+    /// The category of the messages, which are posted when the event occurs and is resolved
+    pub category: MessageCategory,
+    /// A description of the event. The placeholder '{village}' will be replaced by the name of
+    /// the affected village.
+    pub description: String,
+    /// The probability of the event to occur within a single tick, if all of its conditions hold
+    pub probability: f64,
+    /// A set of conditions, which must all hold for the event to occur
+    pub conditions: Vec<Condition>,
+    /// The choices, which the player is offered as reaction to the event
+    pub choices: Vec<Choice>,
+}
+
+/// A condition, which must hold for a RandomEvent to occur
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum Condition {
+    /// The affected village must have at least this number of citizens
+    MinCitizens(i32),
+    /// The affected village must have at most this number of citizens
+    MaxCitizens(i32),
+    /// The treasury must contain at least this amount of gold
+    MinTreasury(i32),
+}
+
+/// A choice, which is offered to the player as reaction to a RandomEvent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Choice {
+    /// The label of the choice, which is displayed on the event screen
+    pub label: String,
+    /// The consequences, which will be applied to the kingdom if the player picks this choice
+    pub consequences: Vec<Consequence>,
+}
+
+/// A consequence of a Choice, which mutates the kingdom
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum Consequence {
+    /// Adds (or removes) a number of citizens to the affected village
+    Citizens(i32),
+    /// Adds (or removes) a percentage of the affected village's citizens
+    CitizensPercent(i32),
+    /// Adds (or removes) gold to the treasury
+    Treasury(i32),
+    /// Raises (or lowers) the level of every craft of a type
+    #[serde(rename_all = "PascalCase")]
+    CraftLevel { craft_type: CraftType, levels: i32 },
+}
+
+/// A RandomEvent, which has occurred in a village and awaits the player's choice
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PendingEvent {
+    /// The event, which has occurred
+    pub event: RandomEvent,
+    /// The index of the affected village
+    pub village: usize,
+}
+
+/// Parses the JSON-content of the asset 'events.json' into a list of random events.
+/// # Arguments
+/// * `json` is the content of the asset
+pub fn load_event_catalogue(json: &str) -> Result<Vec<RandomEvent>, String> {
+
+    let catalogue: Vec<RandomEvent> = serde_json::from_str(json)
+        .map_err(|e| format!("Random events are not well-formatted: {e}"))?;
+
+    if let Some(event) = catalogue.iter().find(|e| !(0.0..=1.0).contains(&e.probability)) {
+        return Err(format!("The probability of the random event '{}' must lie between 0 and 1", event.name));
+    }
+
+    Ok(catalogue)
+}
+
+impl RandomEvent {
+
+    /// Returns true, if all conditions of the event hold for the given village. An event never
+    /// occurs in a village, which doesn't exist.
+    /// # Arguments
+    /// * `kingdom` is the kingdom, in which the event may occur
+    /// * `village` is the index of the village, which would be affected
+    fn is_triggered(&self, kingdom: &Kingdom, village: usize) -> bool {
+
+        let Some(num_citizen) = kingdom.villages.get(village).map(|v| v.num_citizen) else {
+            return false;
+        };
+
+        self.conditions.iter().all(|condition| match condition {
+            Condition::MinCitizens(min) => num_citizen >= *min,
+            Condition::MaxCitizens(max) => num_citizen <= *max,
+            Condition::MinTreasury(min) => kingdom.treasury >= *min,
+        })
+    }
+}
+
+impl PendingEvent {
+
+    /// Returns the event's description for the affected village
+    /// # Arguments
+    /// * `kingdom` is the kingdom, in which the event has occurred
+    pub fn description(&self, kingdom: &Kingdom) -> String {
+
+        let village_name = kingdom.villages.get(self.village)
+            .map(|v| v.name.as_str())
+            .unwrap_or("a village");
+
+        self.event.description.replace("{village}", village_name)
+    }
+}
+
+/// Rolls the dice for every event of the catalogue in order and turns the first one, which occurs,
+/// into the game state's pending event. No new events will occur as long as the player has not
+/// reacted to a pending event.
+/// # Arguments
+/// * `game_state` is the state, in which the events may occur. Its catalogue lists every event.
+pub fn roll_random_events(game_state: &mut GameState) {
+
+    if game_state.pending_event.is_some() || game_state.kingdom.villages.is_empty() {
+        return;
+    }
+
+    let catalogue = Arc::clone(&game_state.event_catalogue);
+
+    for event in catalogue.iter() {
+
+        let village = game_state.rng.gen_index(game_state.kingdom.villages.len());
+
+        if event.is_triggered(&game_state.kingdom, village) && game_state.rng.gen_bool(event.probability) {
+
+            let pending_event = PendingEvent { event: event.clone(), village };
+            let text = format!("{}: {}", event.name, pending_event.description(&game_state.kingdom));
+
+            log::info!(event = event.name.as_str(), village; "Random event occurred");
+
+            game_state.pending_event = Some(pending_event);
+            game_state.post_message(event.category, text);
+            return;
+        }
+    }
+}
+
+/// Applies the consequences of the player's choice to the kingdom and clears the pending event.
+/// Nothing happens, if there is no pending event or the choice does not exist. The consequences
+/// for the affected village are skipped, if the village doesn't exist (anymore), e.g. because the
+/// save file has been edited.
+/// # Arguments
+/// * `game_state` is the state, which contains the pending event
+/// * `choice` is the index of the player's choice
+pub fn resolve_pending_event(game_state: &mut GameState, choice: usize) {
+
+    if !matches!(&game_state.pending_event, Some(pending) if choice < pending.event.choices.len()) {
+        return;
+    }
+
+    let pending = game_state.pending_event.take().unwrap();
+
+    let choice = &pending.event.choices[choice];
+    let kingdom = &mut game_state.kingdom;
+
+    for consequence in &choice.consequences {
+        match consequence {
+            Consequence::Citizens(citizen) => {
+                kingdom.add_citizen(pending.village, *citizen);
+            }
+            Consequence::CitizensPercent(percent) => {
+                if let Some(num_citizen) = kingdom.villages.get(pending.village).map(|v| v.num_citizen) {
+                    kingdom.add_citizen(pending.village, num_citizen * percent / 100);
+                }
+            }
+            Consequence::Treasury(gold) => kingdom.add_treasury(*gold),
+            Consequence::CraftLevel { craft_type, levels } => kingdom.raise_craft_levels(*craft_type, *levels),
+        }
+    }
+
+    log::info!(event = pending.event.name.as_str(), choice = choice.label.as_str(); "Random event resolved");
+
+    game_state.post_message(pending.event.category, format!("{}: You chose '{}'.", pending.event.name, choice.label));
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::data::saves::load_save_file;
+
+    /// Returns a pending event for the given village, whose only choice has the given consequences
+    /// # Arguments
+    /// * `village` is the index of the affected village
+    /// * `consequences` are the consequences of the choice
+    fn pending_event(village: usize, consequences: Vec<Consequence>) -> PendingEvent {
+
+        let choice = Choice { label: String::from("Accept"), consequences };
+        let event = RandomEvent {
+            name: String::from("Test"),
+            category: MessageCategory::Economy,
+            description: String::from("Something happens in {village}"),
+            probability: 1.0,
+            conditions: vec![],
+            choices: vec![choice],
+        };
+
+        PendingEvent { event, village }
+    }
+
+    #[test]
+    fn consequences_for_a_missing_village_are_skipped() {
+
+        let mut game_state = load_save_file("k_best_kingdom.json").unwrap();
+        let missing_village = game_state.kingdom.villages.len();
+        let citizens: Vec<i32> = game_state.kingdom.villages.iter().map(|v| v.num_citizen).collect();
+        let treasury = game_state.kingdom.treasury;
+
+        let consequences = vec![Consequence::Citizens(10), Consequence::CitizensPercent(50), Consequence::Treasury(5)];
+        game_state.pending_event = Some(pending_event(missing_village, consequences));
+
+        resolve_pending_event(&mut game_state, 0);
+
+        assert!(game_state.pending_event.is_none());
+        assert_eq!(game_state.kingdom.villages.iter().map(|v| v.num_citizen).collect::<Vec<i32>>(), citizens);
+        assert_eq!(game_state.kingdom.treasury, treasury + 5);
+    }
+
+    #[test]
+    fn events_never_occur_in_a_missing_village() {
+
+        let game_state = load_save_file("k_best_kingdom.json").unwrap();
+        let event = pending_event(0, vec![]).event;
+
+        assert!(event.is_triggered(&game_state.kingdom, 0));
+        assert!(!event.is_triggered(&game_state.kingdom, game_state.kingdom.villages.len()));
+    }
+}
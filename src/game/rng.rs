@@ -0,0 +1,54 @@
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg32;
+use serde::{Deserialize, Serialize};
+
+/// The seed, which is used for save files that were written before the random number generator
+/// became part of the game state
+pub const DEFAULT_SEED: u64 = 0x5241_494C_414E;
+
+/// A seeded random number generator, which is owned by the GameState. Every simulation system must
+/// draw its randomness from this generator, so that running the game from the same save file will
+/// always lead to the same results. Its complete state is written to and read from save files.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SimulationRng {
+    /// The seed, with which the generator was initialised
+    seed: u64,
+    /// The current internal state of the generator
+    state: Pcg32,
+}
+
+impl SimulationRng {
+
+    /// Creates a new generator from the given seed
+    /// # Arguments
+    /// * `seed` is the seed of the new generator
+    pub fn new(seed: u64) -> SimulationRng {
+        SimulationRng {
+            seed,
+            state: Pcg32::seed_from_u64(seed),
+        }
+    }
+
+    /// Returns a random index within `0..len`. The length must not be 0.
+    /// # Arguments
+    /// * `len` is the length of the collection, which is indexed
+    pub fn gen_index(&mut self, len: usize) -> usize {
+        self.state.gen_range(0..len)
+    }
+
+    /// Returns true with the given probability, which must lie between 0 and 1
+    /// # Arguments
+    /// * `probability` is the probability of returning true
+    pub fn gen_bool(&mut self, probability: f64) -> bool {
+        self.state.gen_bool(probability)
+    }
+}
+
+impl Default for SimulationRng {
+
+    /// Creates a generator from the DEFAULT_SEED
+    fn default() -> Self {
+        SimulationRng::new(DEFAULT_SEED)
+    }
+}
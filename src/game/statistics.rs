@@ -0,0 +1,258 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::craft::CraftType;
+use crate::data::kingdom::Kingdom;
+
+/// The number of samples, which are kept in the history. At one sample per tick, it covers ten
+/// years of the game.
+pub const HISTORY_CAPACITY: usize = 3650;
+
+/// The number of samples, which are stored together in a chunk of the history
+const CHUNK_SIZE: usize = 64;
+
+/// Only the sample of every n-th tick is written to save files, so that they stay small. Hence, a
+/// loaded history is coarser than the one, which is recorded while playing.
+const SAVED_SAMPLE_INTERVAL: u64 = 10;
+
+/// The statistics of the kingdom at the end of a single tick. There are no stocks of resources,
+/// since the kingdom doesn't store any goods yet: The crafts only have a level, and production
+/// pays straight into the treasury. Hence, the treasury and the levels of the crafts stand in for
+/// the resources, until the kingdom keeps stocks, which can be sampled as well.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Sample {
+    /// The tick, at whose end the sample was taken
+    pub tick: u64,
+    /// The gold in the treasury
+    pub treasury: i32,
+    /// The number of citizens of every village in the order of the kingdom's villages
+    pub citizens: Vec<i32>,
+    /// The summed up levels of every type of craft
+    pub craft_levels: Vec<(CraftType, i32)>,
+}
+
+impl Sample {
+
+    /// Returns the summed up levels of the given type of craft, or None, if the kingdom had no
+    /// such craft, when the sample was taken
+    /// # Arguments
+    /// * `craft_type` is the type of craft
+    pub fn craft_level(&self, craft_type: CraftType) -> Option<i32> {
+        self.craft_levels.iter()
+            .find(|(t, _)| *t == craft_type)
+            .map(|(_, level)| *level)
+    }
+}
+
+/// The history of the kingdom's statistics, the oldest sample first. It is a ring buffer, which
+/// drops the oldest sample, once it is full. The samples are stored in chunks, which are shared
+/// between the snapshots of the game state. Only the last chunk is ever changed and thus copied,
+/// so that publishing a snapshot doesn't copy the whole history.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(from = "Vec<Sample>", into = "Vec<Sample>")]
+pub struct StatisticsHistory {
+    /// The chunks of recorded samples, the oldest one first. Every chunk but the last one is full.
+    chunks: VecDeque<Arc<Vec<Arc<Sample>>>>,
+    /// The number of samples at the start of the first chunk, which have already been dropped
+    dropped: usize,
+}
+
+impl StatisticsHistory {
+
+    /// Takes a sample of the kingdom's statistics and appends it to the history
+    /// # Arguments
+    /// * `tick` is the current tick
+    /// * `kingdom` is the kingdom, whose statistics are recorded
+    pub fn record(&mut self, tick: u64, kingdom: &Kingdom) {
+
+        let sample = Sample {
+            tick,
+            treasury: kingdom.treasury,
+            citizens: kingdom.villages.iter().map(|v| v.num_citizen).collect(),
+            craft_levels: kingdom.crafts.iter()
+                .map(|(craft_type, crafts)| (*craft_type, crafts.iter().map(|c| c.lvl).sum()))
+                .collect(),
+        };
+
+        self.push(Arc::new(sample));
+    }
+
+    /// Appends a sample to the history and drops the oldest one, if the history is full
+    /// # Arguments
+    /// * `sample` is the sample, which is appended
+    fn push(&mut self, sample: Arc<Sample>) {
+
+        if self.chunks.back().is_none_or(|chunk| chunk.len() == CHUNK_SIZE) {
+            self.chunks.push_back(Arc::new(Vec::with_capacity(CHUNK_SIZE)));
+        }
+
+        // A full chunk is never changed again, so only the last chunk may have to be copied
+        Arc::make_mut(self.chunks.back_mut().unwrap()).push(sample);
+
+        if self.len() > HISTORY_CAPACITY {
+
+            self.dropped += 1;
+
+            if self.dropped == CHUNK_SIZE {
+                self.chunks.pop_front();
+                self.dropped = 0;
+            }
+        }
+    }
+
+    /// Returns the number of samples in the history
+    pub fn len(&self) -> usize {
+        self.chunks.iter().map(|chunk| chunk.len()).sum::<usize>() - self.dropped
+    }
+
+    /// Returns true, if nothing has been recorded yet
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns every sample of the history, the oldest one first
+    fn samples(&self) -> impl Iterator<Item = &Sample> {
+        self.chunks.iter()
+            .flat_map(|chunk| chunk.iter())
+            .skip(self.dropped)
+            .map(|sample| sample.as_ref())
+    }
+
+    /// Returns every sample, which has been taken since the given tick, the oldest one first
+    /// # Arguments
+    /// * `tick` is the first tick, whose sample is returned
+    pub fn since(&self, tick: u64) -> impl Iterator<Item = &Sample> {
+        self.samples().skip_while(move |sample| sample.tick < tick)
+    }
+
+    /// Returns the most recent sample, or None, if nothing has been recorded yet
+    pub fn latest(&self) -> Option<&Sample> {
+        self.chunks.back()
+            .and_then(|chunk| chunk.last())
+            .map(|sample| sample.as_ref())
+    }
+}
+
+impl From<Vec<Sample>> for StatisticsHistory {
+
+    /// Restores the history from a save file. If the file contains more samples than the history
+    /// can keep, the oldest ones are dropped.
+    fn from(samples: Vec<Sample>) -> StatisticsHistory {
+
+        let mut history = StatisticsHistory::default();
+
+        for sample in samples {
+            history.push(Arc::new(sample));
+        }
+
+        history
+    }
+}
+
+impl From<StatisticsHistory> for Vec<Sample> {
+
+    /// Down-samples the history for a save file. Only the sample of every n-th tick as well as the
+    /// oldest and the most recent one are kept, so that the saved history spans the same time.
+    fn from(history: StatisticsHistory) -> Vec<Sample> {
+
+        let first_tick = history.samples().next().map(|sample| sample.tick);
+        let latest_tick = history.latest().map(|sample| sample.tick);
+
+        history.samples()
+            .filter(|sample| sample.tick.is_multiple_of(SAVED_SAMPLE_INTERVAL)
+                || Some(sample.tick) == first_tick
+                || Some(sample.tick) == latest_tick)
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::data::saves::load_save_file;
+    use crate::game::GameState;
+
+    /// Returns the ticks of the given samples
+    /// # Arguments
+    /// * `samples` are the samples
+    fn ticks<'a>(samples: impl Iterator<Item = &'a Sample>) -> Vec<u64> {
+        samples.map(|sample| sample.tick).collect()
+    }
+
+    /// Returns a history, which has recorded the given ticks of the kingdom of the save file
+    /// # Arguments
+    /// * `ticks` are the ticks, which are recorded
+    fn history(ticks: impl Iterator<Item = u64>) -> StatisticsHistory {
+
+        let kingdom = load_save_file("k_best_kingdom.json").unwrap().kingdom;
+        let mut history = StatisticsHistory::default();
+
+        for tick in ticks {
+            history.record(tick, &kingdom);
+        }
+
+        history
+    }
+
+    #[test]
+    fn a_full_history_drops_the_oldest_samples() {
+
+        let recorded = HISTORY_CAPACITY as u64 + 2 * CHUNK_SIZE as u64 + 7;
+        let history = history(1..=recorded);
+
+        assert_eq!(history.len(), HISTORY_CAPACITY);
+        assert_eq!(history.since(0).next().unwrap().tick, recorded - HISTORY_CAPACITY as u64 + 1);
+        assert_eq!(history.latest().unwrap().tick, recorded);
+        assert_eq!(ticks(history.since(recorded - 2)), vec![recorded - 2, recorded - 1, recorded]);
+    }
+
+    #[test]
+    fn the_saved_history_is_down_sampled_but_keeps_the_first_and_the_last_sample() {
+
+        let saved: Vec<Sample> = history(3..=25).into();
+
+        assert_eq!(ticks(saved.iter()), vec![3, 10, 20, 25]);
+    }
+
+    #[test]
+    fn the_saved_history_never_exceeds_the_capacity() {
+
+        let template = history(0..=0).latest().unwrap().clone();
+        let samples: Vec<Sample> = (1..=HISTORY_CAPACITY as u64 + 100)
+            .map(|tick| Sample { tick, ..template.clone() })
+            .collect();
+
+        let loaded = StatisticsHistory::from(samples);
+        let saved: Vec<Sample> = loaded.clone().into();
+
+        assert_eq!(loaded.len(), HISTORY_CAPACITY);
+        assert!(saved.len() <= HISTORY_CAPACITY);
+        assert_eq!(saved.first().unwrap().tick, loaded.since(0).next().unwrap().tick);
+        assert_eq!(saved.last().unwrap().tick, HISTORY_CAPACITY as u64 + 100);
+    }
+
+    #[test]
+    fn the_history_survives_a_save() {
+
+        let mut game_state = load_save_file("k_best_kingdom.json").unwrap();
+        game_state.statistics = StatisticsHistory::default();
+
+        for tick in 1..=42 {
+            game_state.statistics.record(tick, &game_state.kingdom);
+        }
+
+        let loaded: GameState = serde_json::from_str(&serde_json::to_string(&game_state).unwrap()).unwrap();
+
+        assert_eq!(ticks(loaded.statistics.since(0)), vec![1, 10, 20, 30, 40, 42]);
+
+        let saved = loaded.statistics.latest().unwrap();
+        let recorded = game_state.statistics.latest().unwrap();
+        assert_eq!((saved.treasury, &saved.citizens), (recorded.treasury, &recorded.citizens));
+        assert_eq!(saved.craft_levels, recorded.craft_levels);
+    }
+}
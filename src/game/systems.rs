@@ -0,0 +1,19 @@
+mod population;
+mod production;
+mod statistics;
+
+use super::GameState;
+use super::random_events;
+
+/// A system advances one aspect of the simulation by a single tick, e.g. the population
+pub type System = fn(&mut GameState);
+
+/// Every system of the simulation in the order, in which they run every tick. Citizens move into
+/// the villages first, so that the crafts produce and the random events occur for the grown kingdom.
+/// The statistics are recorded last, once the kingdom has changed.
+pub const SYSTEMS: [System; 4] = [
+    population::run,
+    production::run,
+    random_events::roll_random_events,
+    statistics::run,
+];
@@ -0,0 +1,22 @@
+use crate::game::GameState;
+use crate::game::messages::MessageCategory;
+
+/// Lets a new citizen move into a random village. Whenever a village reaches a multiple of a
+/// hundred citizens, a message is posted.
+/// # Arguments
+/// * `game_state` is the game state, whose population grows
+pub fn run(game_state: &mut GameState){
+
+    if game_state.kingdom.villages.is_empty() {
+        return;
+    }
+
+    let village = game_state.rng.gen_index(game_state.kingdom.villages.len());
+    game_state.kingdom.add_citizen(village, 1);
+
+    let village = &game_state.kingdom.villages[village];
+    if village.num_citizen % 100 == 0 {
+        let text = format!("{} has grown to {} citizens.", village.name, village.num_citizen);
+        game_state.post_message(MessageCategory::Economy, text);
+    }
+}
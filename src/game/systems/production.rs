@@ -0,0 +1,25 @@
+use crate::game::GameState;
+
+/// The number of ticks, after which the crafts sell their goods
+const PRODUCTION_INTERVAL: u64 = 30;
+
+/// The number of levels of a craft, which earn a single gold per production interval
+const LEVELS_PER_GOLD: i32 = 10;
+
+/// Adds the gold, which the crafts have earned, to the treasury once per production interval.
+/// Every craft earns one gold per ten levels.
+/// # Arguments
+/// * `game_state` is the game state, whose crafts produce
+pub fn run(game_state: &mut GameState){
+
+    if !game_state.ticks.is_multiple_of(PRODUCTION_INTERVAL) {
+        return;
+    }
+
+    let gold: i32 = game_state.kingdom.crafts.values()
+        .flatten()
+        .map(|c| c.lvl / LEVELS_PER_GOLD)
+        .sum();
+
+    game_state.kingdom.add_treasury(gold);
+}
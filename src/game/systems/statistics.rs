@@ -0,0 +1,8 @@
+use crate::game::GameState;
+
+/// Records the statistics of the kingdom at the end of the tick, so that their trends can be plotted
+/// # Arguments
+/// * `game_state` is the game state, whose statistics are recorded
+pub fn run(game_state: &mut GameState){
+    game_state.statistics.record(game_state.ticks, &game_state.kingdom);
+}
@@ -0,0 +1,261 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use log::{LevelFilter, Log, Metadata, Record};
+use log::kv::{self, Key, Value, VisitSource};
+use serde::Serialize;
+
+/// The size in bytes, from which on the log file is rotated
+const MAX_LOG_FILE_SIZE: u64 = 1024 * 1024;
+
+/// The number of rotated log files, which are kept next to the current one, e.g. 'railan.log.1'
+const ROTATED_LOG_FILES: u32 = 3;
+
+/// A single entry of the log, which is written as one line of JSON
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct LogEntry<'a> {
+    /// The time of the entry in milliseconds since the unix epoch
+    time: u128,
+    /// The level of the entry, e.g. INFO
+    level: &'a str,
+    /// The thread, which has written the entry
+    thread: &'a str,
+    /// The module, which has written the entry
+    target: &'a str,
+    /// The message of the entry
+    message: String,
+    /// The structured key-values of the entry, e.g. the number of a tick
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    fields: BTreeMap<String, serde_json::Value>,
+}
+
+/// Collects the key-values of a log record. Numbers and booleans are kept, while every other value
+/// is formatted as a string.
+struct FieldCollector(BTreeMap<String, serde_json::Value>);
+
+impl<'kvs> VisitSource<'kvs> for FieldCollector {
+
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> {
+        let value = if let Some(number) = value.to_u64() {
+            serde_json::Value::from(number)
+        } else if let Some(number) = value.to_i64() {
+            serde_json::Value::from(number)
+        } else if let Some(number) = value.to_f64() {
+            serde_json::Value::from(number)
+        } else if let Some(boolean) = value.to_bool() {
+            serde_json::Value::from(boolean)
+        } else {
+            serde_json::Value::from(value.to_string())
+        };
+
+        self.0.insert(key.to_string(), value);
+        Ok(())
+    }
+}
+
+/// A log file, which is moved aside once it has grown too large. The rotated files are numbered,
+/// the most recent one first, while the oldest one is removed.
+struct RotatingFile {
+    /// The path of the current log file
+    path: PathBuf,
+    /// The current log file, which is appended to
+    file: File,
+    /// The size of the current log file in bytes
+    size: u64,
+}
+
+impl RotatingFile {
+
+    /// Opens a log file for appending. It is created, if it does not exist.
+    /// # Arguments
+    /// * `path` is the path of the log file
+    fn open(path: &Path) -> io::Result<RotatingFile> {
+
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+
+        Ok(RotatingFile { path: path.to_path_buf(), file, size })
+    }
+
+    /// Appends a line to the log file and rotates it beforehand, if it would grow too large
+    /// # Arguments
+    /// * `line` is the line, which is appended
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+
+        if self.size > 0 && self.size + line.len() as u64 >= MAX_LOG_FILE_SIZE {
+            self.rotate()?;
+        }
+
+        writeln!(self.file, "{line}")?;
+        self.size += line.len() as u64 + 1;
+        Ok(())
+    }
+
+    /// Shifts every rotated file by one, moves the current file to the first place and starts a
+    /// new one
+    fn rotate(&mut self) -> io::Result<()> {
+
+        for number in (1..ROTATED_LOG_FILES).rev() {
+            let from = self.rotated_path(number);
+            if from.exists() {
+                fs::rename(from, self.rotated_path(number + 1))?;
+            }
+        }
+
+        fs::rename(&self.path, self.rotated_path(1))?;
+
+        self.file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+
+    /// Returns the path of a rotated file, e.g. 'railan.log.2'
+    /// # Arguments
+    /// * `number` is the number of the rotated file
+    fn rotated_path(&self, number: u32) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{number}"));
+        PathBuf::from(path)
+    }
+}
+
+/// A logger, which writes structured entries to a rotating log file. It never writes to the
+/// terminal, as that would garble the terminal-ui.
+struct FileLogger {
+    /// The most detailed level, which is logged
+    level: LevelFilter,
+    /// The log file, which is shared by every thread
+    file: Mutex<RotatingFile>,
+}
+
+impl Log for FileLogger {
+
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let mut fields = FieldCollector(BTreeMap::new());
+        let _ = record.key_values().visit(&mut fields);
+
+        let time = SystemTime::now().duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or_default();
+
+        let current_thread = thread::current();
+
+        let entry = LogEntry {
+            time,
+            level: record.level().as_str(),
+            thread: current_thread.name().unwrap_or("unnamed"),
+            target: record.target(),
+            message: record.args().to_string(),
+            fields: fields.0,
+        };
+
+        // A log entry, which can't be written, is dropped, as there is nowhere else to report it
+        if let Ok(line) = serde_json::to_string(&entry) {
+            if let Ok(mut file) = self.file.lock() {
+                let _ = file.write_line(&line);
+            }
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.file.flush();
+        }
+    }
+}
+
+/// Starts logging to the given file. Without a log file, nothing is logged. This function must be
+/// called at most once and before any thread is started.
+/// # Arguments
+/// * `path` is the path of the log file
+/// * `level` is the most detailed level, which is logged
+pub fn init_logging(path: Option<&Path>, level: LevelFilter) -> Result<(), String> {
+
+    let Some(path) = path else {
+        return Ok(());
+    };
+
+    let file = RotatingFile::open(path)
+        .map_err(|e| format!("Unable to open the log file '{}': {e}", path.display()))?;
+
+    log::set_boxed_logger(Box::new(FileLogger { level, file: Mutex::new(file) }))
+        .map_err(|e| format!("Unable to start logging: {e}"))?;
+
+    log::set_max_level(level);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /// Returns an empty directory for the files of a single test
+    /// # Arguments
+    /// * `name` is the name of the test
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("railan-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn rotates_at_the_size_limit_and_prunes_the_oldest_files() {
+
+        let dir = test_dir("rotation");
+        let path = dir.join("railan.log");
+        let line = "x".repeat(999);
+        let lines_per_file = (MAX_LOG_FILE_SIZE / 1000) as usize;
+
+        let mut file = RotatingFile::open(&path).unwrap();
+
+        // Fill one file more than is kept, so that the oldest one has to be pruned
+        for _ in 0..lines_per_file * (ROTATED_LOG_FILES as usize + 2) {
+            file.write_line(&line).unwrap();
+        }
+
+        for number in 1..=ROTATED_LOG_FILES {
+            let rotated = file.rotated_path(number);
+            assert!(rotated.exists(), "{} is missing", rotated.display());
+            assert!(fs::metadata(&rotated).unwrap().len() <= MAX_LOG_FILE_SIZE);
+        }
+
+        assert!(!file.rotated_path(ROTATED_LOG_FILES + 1).exists());
+        assert!(fs::metadata(&path).unwrap().len() <= MAX_LOG_FILE_SIZE);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn appends_to_an_existing_log_file() {
+
+        let dir = test_dir("append");
+        let path = dir.join("railan.log");
+        fs::write(&path, "first\n").unwrap();
+
+        let mut file = RotatingFile::open(&path).unwrap();
+        file.write_line("second").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "first\nsecond\n");
+        assert_eq!(file.size, 13);
+        assert!(!file.rotated_path(1).exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
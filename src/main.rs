@@ -1,58 +1,159 @@
+mod cli;
+mod logging;
 mod menu;
 mod data;
 mod game;
 mod threadcom;
 
-use game::GameState;
 use threadcom::ThreadCommunication;
 
+use arc_swap::ArcSwap;
+use clap::Parser;
+
+use std::fmt::Display;
+use std::process;
 use std::thread;
-use std::sync::{Arc, RwLock};
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+/// Prints an error, which has occurred before the terminal-ui starts, and exits the game
+/// # Arguments
+/// * `error` is the error, which is printed
+fn exit_with_error(error: impl Display) -> !{
+    eprintln!("error: {error}");
+    process::exit(1);
+}
 
-/// Opens a terminal application game, which runs three separate threads:
+/// Starts a thread, whose name appears in the log
+/// # Arguments
+/// * `name` is the name of the thread
+/// * `f` is the function, which the thread runs
+fn spawn_thread<F: FnOnce() + Send + 'static>(name: &str, f: F) -> thread::JoinHandle<()>{
+    thread::Builder::new().name(String::from(name)).spawn(f)
+        .unwrap_or_else(|e| panic!("Unable to start the thread '{name}': {e}"))
+}
+
+/// Opens a terminal application game, which runs two separate threads:
 /// 1) A thread running the game-loop
-/// 2) A thread running an listener for key inputs
-/// 3) A thread running the terminal renderer
-/// Upon a player quitting the terminal, every thread receives a "quit"-token and quits graciously.
+/// 2) A thread running the terminal renderer, which also listens for input events
+///
+/// Upon a player quitting the terminal, the game-loop receives a "quit"-token and quits graciously.
+///
+/// If the game is started with '--headless <ticks>', no thread is started. Instead, the simulation
+/// runs for the given number of ticks and its result is written to stdout or the '--output'-file.
 ///
 /// In order to facilitate the communication between threads, a ThreadCommunication-struct is used.
 /// Please have a look at its documentation.
 fn main(){
 
-    // Load the provided save-file
-    let kingdom = data::saves::load_save_file("k_best_kingdom.json");
-    let game_state = Arc::new(RwLock::new(GameState::new(kingdom)));
+    // Invalid arguments, --help and --version are handled by clap, which exits afterwards
+    let cli = cli::Cli::parse();
 
-    let mut thread_communication = ThreadCommunication::new();
+    // Use the assets of an override directory, if one is configured
+    menu::assets::init_override_dir(cli.assets_dir)
+        .unwrap_or_else(|e| exit_with_error(e));
 
-    thread_communication.add_handle({
+    data::saves::init_saves_dir(cli.saves_dir);
 
-        let quit_rc = thread_communication.get_quit_rx();
-        let game_state = Arc::clone(&game_state);
+    // Start logging first, so that the whole startup is logged
+    logging::init_logging(cli.log_file.as_deref(), cli.log_level)
+        .unwrap_or_else(|e| exit_with_error(e));
 
-        thread::spawn(move|| game::game_loop::game_loop(game_state, quit_rc) )
-    });
+    log::info!(version = env!("CARGO_PKG_VERSION"); "Railan started");
+
+    // Load the provided save-file or the state, in which the replayed recording has started
+    let (mut game_state, replay) = match &cli.replay {
+        Some(path) => {
+            let (replay, game_state) = menu::input_recording::Replay::load(path)
+                .unwrap_or_else(|e| exit_with_error(e));
+            (game_state, Some(replay))
+        }
+        None => {
+            let game_state = data::saves::load_save_file(&cli.save)
+                .unwrap_or_else(|e| exit_with_error(e));
+            (game_state, None)
+        }
+    };
+
+    if let Some(seed) = cli.seed {
+        game_state.rng = game::rng::SimulationRng::new(seed);
+    }
+
+    // Load every random event, which may occur during the game
+    game_state.event_catalogue = menu::assets::read_asset("events.json")
+        .map_err(|e| e.to_string())
+        .and_then(|json| game::random_events::load_event_catalogue(&json))
+        .map(Arc::new)
+        .unwrap_or_else(|e| exit_with_error(e));
+
+    if let Some(ticks) = cli.headless {
+
+        log::info!(ticks; "Headless run started");
+        let summary = game::headless::run_headless(&mut game_state, ticks);
+        log::info!(final_tick = summary.final_tick; "Headless run finished");
+
+        game::headless::write_report(&summary, &game_state, cli.output.as_deref())
+            .unwrap_or_else(|e| exit_with_error(e));
+
+        return;
+    }
+
+    // Load the key bindings, so that any misconfiguration is reported before the terminal-ui starts
+    let key_bindings = menu::key_bindings::KeyBindings::load()
+        .unwrap_or_else(|e| exit_with_error(e));
+
+    // Load the themes, which the player can choose from in the settings
+    let themes = menu::themes::Themes::load()
+        .unwrap_or_else(|e| exit_with_error(e));
+
+    // The recording starts with the state, in which the game starts
+    let recorder = cli.record.as_deref()
+        .map(|path| menu::input_recording::InputRecorder::create(path, &game_state, cli.speed))
+        .transpose()
+        .unwrap_or_else(|e| exit_with_error(e));
+
+    // The game-loop owns the game state, while the terminal-ui reads the snapshots, which it publishes
+    let snapshot = Arc::new(ArcSwap::from_pointee(game_state.clone()));
+
+    // Enter raw mode and the alternate screen. The terminal is restored, when the guard is dropped
+    // at the end of main, if any thread panics or if the process is interrupted or terminated.
+    let _terminal_guard = menu::terminal_guard::TerminalGuard::new()
+        .unwrap_or_else(|e| exit_with_error(format!("Unable to set up the terminal: {e}")));
+
+    let mut thread_communication = ThreadCommunication::new();
+
+    // A replay drives the game instead of the game-loop, until it has finished
+    thread_communication.get_simulation_paused().store(replay.is_some(), Ordering::Release);
 
-    // Start a thread, which listens for input key events
     thread_communication.add_handle({
 
-        let quit_rc = thread_communication.get_quit_rx();
-        let event_tx = thread_communication.get_event_tx();
+        let channels = game::game_loop::GameLoopChannels {
+            quit_rx: thread_communication.get_quit_rx(),
+            command_rx: thread_communication.get_command_rx(),
+            notification_tx: thread_communication.get_notification_tx(),
+            error_tx: thread_communication.get_error_tx(),
+        };
+        let simulation_paused = thread_communication.get_simulation_paused();
+        let snapshot = Arc::clone(&snapshot);
 
-        thread::spawn(move|| menu::event_listener::listen_for_input_events(quit_rc, event_tx)
-        )
+        spawn_thread("game-loop", move|| game::game_loop::game_loop(game_state, snapshot, cli.speed, simulation_paused, channels) )
     });
 
-    // Start a thread, which renders the terminal-ui
+    // Start a thread, which renders the terminal-ui and processes the input events
     thread_communication.add_handle({
 
         let quit_tc = thread_communication.get_quit_tx();
-        let event_rx = thread_communication.get_event_rx();
-        let game_state = Arc::clone(&game_state);
+        let notification_rx = thread_communication.get_notification_rx();
+        let error_rx = thread_communication.get_error_rx();
+        let commands = game::commands::CommandSender::new(thread_communication.get_command_tx());
+        let simulation_paused = thread_communication.get_simulation_paused();
+        let config = menu::UIConfig { key_bindings, themes, save_file_name: cli.save, max_fps: cli.max_fps, recorder, replay };
 
-        thread::spawn(move|| menu::render_terminal(game_state,quit_tc, event_rx))
+        spawn_thread("renderer", move|| menu::render_terminal(snapshot, commands, quit_tc, notification_rx, error_rx, simulation_paused, config))
     });
 
     // Wait for all threads to shut down in an orderly fashion
     thread_communication.wait_on_shutdown();
+
+    log::info!("Railan stopped");
 }
@@ -1,62 +1,247 @@
-pub mod event_listener;
+pub mod input_recording;
+pub mod assets;
+pub mod key_bindings;
+pub mod terminal_guard;
+pub mod themes;
 
 mod events;
 mod user_interface;
 mod current_ui;
 mod ui_foundations;
-mod assets;
 mod rendering;
+mod view_registry;
 
-use crossterm::event::Event;
-use std::sync::{Arc,RwLock};
-use std::time::Duration;
-use crossbeam::channel::{Sender,Receiver};
+pub use ui_foundations::UIError;
+
+use crossterm::event::{Event, EventStream};
+use flume::{Sender,Receiver};
+use futures::{future, select_biased, FutureExt, StreamExt};
+use futures_timer::Delay;
+use arc_swap::ArcSwap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tui::backend::Backend;
 
 use crate::menu::events::UIEventBuilder;
+use crate::menu::input_recording::{InputRecorder, Replay, ReplayStep};
+use crate::menu::key_bindings::KeyBindings;
+use crate::menu::themes::Themes;
 use crate::menu::user_interface::UserInterface;
 use crate::game::GameState;
-use crate::menu::ui_foundations::UIType;
+use crate::game::commands::{Command, CommandSender};
+use crate::game::game_loop::Notification;
+use crate::menu::ui_foundations::{create_crossterm_terminal, UIType};
+
+/// The configuration of the user-interface, which is loaded once at startup
+pub struct UIConfig{
+    /// The key bindings, which map key input events to actions
+    pub key_bindings: KeyBindings,
+    /// The themes, which the player can choose from
+    pub themes: Themes,
+    /// The name of the save file, to which the game is saved
+    pub save_file_name: String,
+    /// The maximum number of frames, which are drawn per second
+    pub max_fps: u32,
+    /// A recorder, which writes every processed input event to a replay file
+    pub recorder: Option<InputRecorder>,
+    /// A replay, whose input events are processed instead of the keyboard's
+    pub replay: Option<Replay>,
+}
 
-/// Renders a terminal in the console. It will continuously render the terminal until the application
+/// Renders a terminal in the console. It will render the terminal until the application
 /// terminates. If the user/player wants to quit the app, this method will recognize the corresponding
-/// UIType::Terminated, and send two quit-tokens to the two other running game threads, essentially
-/// commanding them to shut down.
-/// Any key-event, which is found by a concurrently running thread, will be sent to this thread and
-/// processed within a loop-cycle.
+/// UIType::Terminated, and send a quit-token to the game-loop, essentially commanding it to shut down.
 /// # Arguments
-/// * `game_state` the game data, which manageable in the console
+/// * `game_state` the snapshot of the game data, which manageable in the console
+/// * `commands` the sender of commands, which change the game data
 /// * `quit_app_tx` the communication channel for sending quit-tokens
-/// * `event_rx`the communication channel for receiving input events
-pub fn render_terminal(game_state: Arc<RwLock<GameState>>, quit_app_tx: Sender<String>, event_rx: Receiver<Event>){
+/// * `notification_rx` the communication channel for receiving messages and changes from the game-loop
+/// * `error_rx` the communication channel for receiving recoverable errors from any thread
+/// * `simulation_paused` the flag, which pauses the game-loop during a replay. It is cleared, once
+///   the replay has finished.
+/// * `config` the configuration of the user-interface
+pub fn render_terminal(game_state: Arc<ArcSwap<GameState>>, commands: CommandSender, quit_app_tx: Sender<String>, notification_rx: Receiver<Notification>, error_rx: Receiver<UIError>, simulation_paused: Arc<AtomicBool>, config: UIConfig){
+
+    log::info!("Renderer started");
+
+    futures::executor::block_on(run_user_interface(game_state, commands, notification_rx, error_rx, simulation_paused, config));
+
+    quit_app_tx.send(String::from("quit")).expect("Should send quit");
+    log::info!("Renderer stopped");
+}
+
+/// Runs the user-interface until the player quits. Instead of polling, it sleeps until an input
+/// event, a notification from the game-loop, an error reported by any thread or a timer wakes it up, e.g. because a toast expires.
+/// The terminal is only redrawn, if the game state or the view has changed since the last time.
+/// Changes of the game state aren't drawn more often than the maximum frame rate allows.
+/// While a replay runs, it drives the game instead of the game-loop, and the keyboard is ignored
+/// apart from resizing the terminal. The recorded resize events are skipped in turn, as the size of
+/// the terminal can't be replayed.
+/// # Arguments
+/// * `game_state` the snapshot of the game data, which manageable in the console
+/// * `commands` the sender of commands, which change the game data
+/// * `notification_rx` the communication channel for receiving messages and changes from the game-loop
+/// * `error_rx` the communication channel for receiving recoverable errors from any thread
+/// * `simulation_paused` the flag, which pauses the game-loop during a replay
+/// * `config` the configuration of the user-interface
+async fn run_user_interface(game_state: Arc<ArcSwap<GameState>>, commands: CommandSender, notification_rx: Receiver<Notification>, error_rx: Receiver<UIError>, simulation_paused: Arc<AtomicBool>, mut config: UIConfig){
 
-    let mut ui = UserInterface::new(game_state);
+    let mut recorder = config.recorder.take();
+    let mut replay = config.replay.take();
+    let frame_interval = Duration::from_secs(1) / config.max_fps;
+    let mut next_frame_time = Instant::now();
+
+    let mut ui = UserInterface::new(create_crossterm_terminal(), Arc::clone(&game_state), commands.clone(), config);
+    let mut input_events = EventStream::new();
+
+    // A changed view is drawn at once, as the player expects an immediate reaction and the views
+    // rely on their last rendering, e.g. for scrolling. A changed game state may wait for the next frame.
+    let mut has_view_changed = true;
+    let mut has_state_changed = false;
 
     loop {
 
+        // Advance the replay by every step, which is due, but by one input event at most
+        if let Some(active_replay) = &mut replay {
+
+            let finished = loop {
+
+                let tick = game_state.load().ticks;
+
+                match active_replay.next_step(tick) {
+                    // The messages and the change are notified by the game-loop like any other tick
+                    ReplayStep::Tick => commands.execute(Command::Tick),
+                    ReplayStep::Input(Event::Resize(_, _)) => {}
+                    ReplayStep::Input(event) => {
+                        process_input_event(&mut ui, event);
+                        has_view_changed = true;
+                        break false;
+                    }
+                    ReplayStep::Wait => break false,
+                    ReplayStep::Finished => break true,
+                }
+            };
+
+            if finished {
+                log::info!("Finished replaying the input");
+                replay = None;
+                simulation_paused.store(false, Ordering::Release);
+            }
+        }
+
         // Check, if the terminal must be updated and update its state if necessary
-        ui.update_terminal();
+        has_view_changed |= ui.update_terminal();
 
-        // (Re)render the terminal
-        ui.render_terminal();
+        // (Re)render the terminal, but only if anything has changed. Changes of the game state are
+        // drawn together, once the next frame is due.
+        if has_view_changed || (has_state_changed && Instant::now() >= next_frame_time) {
+            ui.render_terminal();
+            has_view_changed = false;
+            has_state_changed = false;
+            next_frame_time = Instant::now() + frame_interval;
+        }
 
-        // In case the user wants to quit, inform the other concurrent threads and terminate yourself.
+        // In case the user wants to quit, stop running, so that the game-loop is informed.
         if ui.get_current_uitype() == UIType::Terminated{
-
-            quit_app_tx.send(String::from("quit")).expect("Should send quit");
-            quit_app_tx.send(String::from("quit")).expect("Should send quit");
             break;
         }
 
-        // Listen for key input events for 200 ms each turn. Process any received event.
-        if let Ok(event) = event_rx.recv_timeout(Duration::new(0,200)) {
+        // Sleep until the terminal changes by itself, the next step of the replay or a held back
+        // frame is due
+        let tick = game_state.load().ticks;
+        let wake_up_time = ui.next_update_time().into_iter()
+            .chain(replay.as_ref().and_then(|r| r.next_step_time(tick)))
+            .chain(has_state_changed.then_some(next_frame_time))
+            .min();
 
-            let event = UIEventBuilder::new().input_key_event(event).build();
+        let mut timer = match wake_up_time {
+            Some(time) => Delay::new(time.saturating_duration_since(Instant::now())).left_future(),
+            None => future::pending().right_future(),
+        }.fuse();
 
-            match event{
-                Ok(event) => ui.process_ui_event(&event),
-                Err(_) => { /* For now, do nothing. In the future, print errors to the UI in some overlay, if possible. */}
-            }
+        // Wait for whatever happens first. An expired timer is handled at the start of the next turn.
+        select_biased! {
+            event = input_events.next().fuse() => match event {
+                Some(Ok(event)) => {
+
+                    if replay.is_some() && !matches!(event, Event::Resize(_, _)) {
+                        continue;
+                    }
+
+                    if let Some(active_recorder) = &mut recorder {
+
+                        let tick = game_state.load().ticks;
+
+                        // A recording, which has failed once, is stopped rather than left with gaps
+                        if let Err(e) = active_recorder.record(tick, &event) {
+                            ui.report_error(UIError::Recording(e));
+                            recorder = None;
+                        }
+                    }
 
+                    process_input_event(&mut ui, event);
+                    has_view_changed = true;
+                }
+                Some(Err(e)) => {
+                    ui.report_error(UIError::Input(e.to_string()));
+                    has_view_changed = true;
+                }
+                None => {
+                    log::warn!("The input has been closed");
+                    break;
+                }
+            },
+            notification = notification_rx.recv_async().fuse() => match notification {
+                Ok(notification) => {
+
+                    // Take every notification, which is pending, so that they are drawn at once
+                    for notification in std::iter::once(notification).chain(notification_rx.try_iter()) {
+                        match notification {
+                            Notification::Message(message) => {
+                                ui.add_notification(message);
+                                has_state_changed = true;
+                            }
+                            Notification::StateChanged(revision) => {
+                                has_state_changed |= !ui.has_drawn_revision(revision);
+                            }
+                        }
+                    }
+                }
+                Err(_) => {
+                    log::warn!("The game-loop has stopped");
+                    break;
+                }
+            },
+            error = error_rx.recv_async().fuse() => match error {
+                Ok(error) => {
+
+                    // Display every error, which is pending, at once
+                    for error in std::iter::once(error).chain(error_rx.try_iter()) {
+                        ui.report_error(error);
+                    }
+                    has_view_changed = true;
+                }
+                Err(_) => {
+                    log::warn!("The error channel has been closed");
+                    break;
+                }
+            },
+            _ = timer => {}
         }
     }
 }
+
+/// Builds a ui-event from an input event and lets the user-interface process it
+/// # Arguments
+/// * `ui` is the user-interface, which processes the event
+/// * `event` is the input event, e.g. a key press
+fn process_input_event<B: Backend>(ui: &mut UserInterface<B>, event: Event){
+
+    let event = UIEventBuilder::new().input_key_event(event).build();
+
+    match event{
+        Ok(event) => ui.process_ui_event(&event),
+        Err(e) => ui.report_error(e),
+    }
+}
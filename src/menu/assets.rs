@@ -1,15 +1,163 @@
+use std::env;
+use std::fmt::{Display, Formatter};
 use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
-pub const ASSET_PATH : &str = "./assets";
+/// The environment variable, which may point to a directory with assets overriding the default ones
+pub const ASSETS_DIR_ENV_VAR : &str = "RAILAN_ASSETS_DIR";
 
-/// Reads a file from the asset-folder and returns its content
+/// The default assets, which are embedded into the binary at compile time
+const EMBEDDED_ASSETS : [(&str, &str); 7] = [
+    ("events.json", include_str!("../../assets/events.json")),
+    ("exit.txt", include_str!("../../assets/exit.txt")),
+    ("keys.json", include_str!("../../assets/keys.json")),
+    ("main.txt", include_str!("../../assets/main.txt")),
+    ("main_menu.txt", include_str!("../../assets/main_menu.txt")),
+    ("themes.json", include_str!("../../assets/themes.json")),
+    ("welcome.txt", include_str!("../../assets/welcome.txt")),
+];
+
+/// The directory, whose assets override the embedded ones. It is set once at startup.
+static OVERRIDE_DIR : OnceLock<Option<PathBuf>> = OnceLock::new();
+
+/// An error, which occurs if an asset cannot be read
+#[derive(Hash,Debug)]
+pub enum AssetError{
+    /// The override directory does not exist
+    MissingOverrideDir(PathBuf),
+    /// The asset exists neither in the override directory nor in the embedded assets
+    NotFound(String),
+    /// The asset exists in the override directory, but cannot be read
+    Unreadable(PathBuf, String),
+}
+
+impl Display for AssetError{
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+
+        match self{
+            AssetError::MissingOverrideDir(dir) => { write!(f, "The asset directory '{}' does not exist", dir.display())}
+            AssetError::NotFound(asset_name) => { write!(f, "There is no asset '{asset_name}'")}
+            AssetError::Unreadable(path, reason) => { write!(f, "Unable to read the asset '{}': {reason}", path.display())}
+        }
+    }
+}
+
+/// Sets the directory, whose assets override the embedded ones. If no directory is given, the
+/// environment variable RAILAN_ASSETS_DIR is used instead, if it is set. This function must be
+/// called at most once and before any asset is read.
 /// # Arguments
+/// * `dir` is the override directory
+pub fn init_override_dir(dir: Option<PathBuf>) -> Result<(), AssetError>{
+
+    let dir = dir.or_else(|| env::var_os(ASSETS_DIR_ENV_VAR).map(PathBuf::from));
+
+    if let Some(dir) = &dir {
+        if !dir.is_dir() {
+            return Err(AssetError::MissingOverrideDir(dir.clone()));
+        }
+    }
+
+    OVERRIDE_DIR.set(dir).expect("The asset directory should only be set once");
+    Ok(())
+}
+
+/// Reads an asset and returns its content. An asset in the override directory takes precedence
+/// over the embedded one, so that single assets can be modded.
+/// # Arguments
+/// * `asset_name` is the name of the file, which should be read.
+pub fn read_asset(asset_name: &str) -> Result<String, AssetError>{
+    read_asset_from(OVERRIDE_DIR.get().and_then(|dir| dir.as_deref()), asset_name)
+}
+
+/// Reads an asset from the given override directory or, if it isn't there, from the embedded assets
+/// # Arguments
+/// * `override_dir` is the directory, whose assets override the embedded ones, if there is any
 /// * `asset_name` is the name of the file, which should be read.
-pub fn read_asset(asset_name: &str) -> String{
+fn read_asset_from(override_dir: Option<&Path>, asset_name: &str) -> Result<String, AssetError>{
+
+    if let Some(dir) = override_dir {
+
+        let path = dir.join(asset_name);
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => return Ok(contents),
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(AssetError::Unreadable(path, e.to_string())),
+        }
+    }
+
+    EMBEDDED_ASSETS.iter()
+        .find(|(name, _)| *name == asset_name)
+        .map(|(_, contents)| String::from(*contents))
+        .ok_or_else(|| AssetError::NotFound(String::from(asset_name)))
+}
+
+/// Returns the path of a configuration file in the user's config directory, that is
+/// $XDG_CONFIG_HOME/railan or $HOME/.config/railan, if the directory is known
+/// # Arguments
+/// * `file_name` is the name of the configuration file
+pub fn user_config_path(file_name: &str) -> Option<PathBuf>{
+
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(config_dir.join("railan").join(file_name))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /// Returns a new, empty directory in the temporary directory
+    /// # Arguments
+    /// * `name` distinguishes the directories of the tests
+    fn temp_dir(name: &str) -> PathBuf {
+
+        let dir = env::temp_dir().join(format!("railan-assets-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn assets_in_the_override_directory_take_precedence() {
+
+        let dir = temp_dir("override");
+        fs::write(dir.join("main.txt"), "Modded").unwrap();
+
+        let modded = read_asset_from(Some(&dir), "main.txt");
+        let embedded = read_asset_from(Some(&dir), "keys.json");
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(modded.unwrap(), "Modded");
+        assert_eq!(embedded.unwrap(), include_str!("../../assets/keys.json"));
+        assert_eq!(read_asset_from(None, "main.txt").unwrap(), include_str!("../../assets/main.txt"));
+    }
+
+    #[test]
+    fn missing_and_unreadable_assets_are_reported() {
+
+        let dir = temp_dir("unreadable");
+        fs::create_dir(dir.join("exit.txt")).unwrap();
+
+        let unreadable = read_asset_from(Some(&dir), "exit.txt");
+        let missing = read_asset_from(Some(&dir), "missing.txt");
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(matches!(unreadable, Err(AssetError::Unreadable(path, _)) if path.ends_with("exit.txt")));
+        assert!(matches!(missing, Err(AssetError::NotFound(name)) if name == "missing.txt"));
+    }
+
+    #[test]
+    fn a_missing_override_directory_is_rejected() {
 
-    let path = format!("{}/{asset_name}",ASSET_PATH);
+        let dir = env::temp_dir().join(format!("railan-assets-missing-{}", std::process::id()));
 
-    let contents = fs::read_to_string( path)
-        .expect(format!("Should have been able to read the asset '{ASSET_PATH}/{}'",asset_name).as_str());
-    contents
+        assert!(matches!(init_override_dir(Some(dir.clone())), Err(AssetError::MissingOverrideDir(d)) if d == dir));
+    }
 }
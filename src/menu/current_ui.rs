@@ -1,53 +1,70 @@
 use std::collections::HashMap;
-use std::io::Stdout;
 use std::ops::Add;
-use std::sync::{Arc, RwLock};
 use std::time::{Instant,Duration};
-use tui::backend::CrosstermBackend;
-use tui::Terminal;
-use tui::widgets::List;
+use tui::backend::Backend;
+use tui::Frame;
 use crate::game::GameState;
+use crate::menu::themes::Theme;
+use crate::menu::view_registry::ViewRegistry;
 
 use crate::menu::ui_foundations::{ScrollingDirection, UIType};
 use crate::menu::rendering::{
     TerminalRenderer,
+    ViewLayout,
     full_screen::FullScreenMessage,
     village_menu::VillageMenu,
     craft_menu::CraftMenu,
     main_menu::MainMenu,
     diplomacy_menu::DiplomacyMenu,
+    message_log_menu::MessageLogMenu,
+    statistics_menu::StatisticsMenu,
+    village_detail_menu::VillageDetailMenu,
+    rename_village_menu::RenameVillageMenu,
+    settings_menu::SettingsMenu,
+    text_input::TextInput,
 };
 
 /// This struct represents the current user-interface, which has a UIType, which specifies the
 /// type of widget, which is displayed, e.g. the widget for displaying the player's villages. This
-/// struct is also used to render the terminal.
-pub struct CurrentUI{
+/// struct is also used to render the terminal. Nested views are pushed onto a navigation history,
+/// so that the player can go back one level at a time.
+pub struct CurrentUI<B: Backend>{
     /// The current type of user-interface
     pub ui_type: UIType,
+    /// The views, which lead to the current view, the root first. Going back returns to the last one.
+    history: Vec<UIType>,
     /// A map containing for every UIType the corresponding rendering-trait,
     /// which will draw the whole terminal, that is, main-widget and menu-widget
-    renderer_for_ui_type: HashMap<UIType,Box<dyn TerminalRenderer>>,
+    renderer_for_ui_type: HashMap<UIType,Box<dyn TerminalRenderer<B>>>,
     /// A possiblle expiratione time, from which on the corresponding UIType is not valid anymore.
     expiration_time: Option<Instant>,
 }
 
-impl CurrentUI{
+impl<B: Backend> CurrentUI<B>{
 
     /// Creates a new CurrentUI struct. Initially it will be of type UIType::Start and contain an
     /// expiration_time of three seconds.
-    pub fn new() -> CurrentUI{
+    /// # Arguments
+    /// * `theme_names` are the names of the themes, which can be chosen in the settings
+    pub fn new(theme_names: Vec<String>) -> CurrentUI<B>{
         CurrentUI{
             ui_type: UIType::Start,
+            history: vec![],
             renderer_for_ui_type: {
-                let mut map : HashMap<UIType,Box<dyn TerminalRenderer>> = HashMap::new();
+                let mut map : HashMap<UIType,Box<dyn TerminalRenderer<B>>> = HashMap::new();
 
                 // add a rendering trait for every UIType
                 map.insert( UIType::Start, Box::new(FullScreenMessage { ui_type: UIType::Start}));
                 map.insert( UIType::Quit, Box::new( FullScreenMessage { ui_type: UIType::Quit} ));
-                map.insert( UIType::Main, Box::new(MainMenu{}));
+                map.insert( UIType::Main, Box::new(MainMenu::new()));
                 map.insert( UIType::Villages, Box::new(VillageMenu::new()));
                 map.insert( UIType::Crafts, Box::new(CraftMenu::new()));
                 map.insert( UIType::Diplomacy, Box::new(DiplomacyMenu::new()));
+                map.insert( UIType::MessageLog, Box::new(MessageLogMenu::new()));
+                map.insert( UIType::Statistics, Box::new(StatisticsMenu::new()));
+                map.insert( UIType::VillageDetail, Box::new(VillageDetailMenu::new()));
+                map.insert( UIType::RenameVillage, Box::new(RenameVillageMenu::new()));
+                map.insert( UIType::Settings, Box::new(SettingsMenu::new(theme_names)));
 
                 map
             },
@@ -67,13 +84,25 @@ impl CurrentUI{
         }
     }
 
+    /// Returns the time, from which on the current ui has expired, or None, if it doesn't expire
+    pub fn get_expiration_time(&self) -> Option<Instant> {
+        self.expiration_time
+    }
+
     /// Updates the UIType of the current ui. In case of type UIType::Quit (the user quits the terminal)
     /// it will add an expiration time of three seconds so that the closing message can be displayed
-    /// as long.
+    /// as long. The navigation history is replaced, so that going back from any view of the menu
+    /// returns to the main view.
     /// * `ui_type` is the new ui-type of the current ui
     pub fn change_ui_type(&mut self, ui_type: UIType){
+        log::debug!(from:? = self.ui_type, to:? = ui_type; "Changed view");
         self.ui_type = ui_type;
 
+        self.history = match ui_type {
+            UIType::Start | UIType::Main | UIType::Quit | UIType::Terminated => vec![],
+            _ => vec![UIType::Main],
+        };
+
         if self.ui_type == UIType::Quit{
             self.expiration_time = Some(Instant::now().add(Duration::from_secs(3)));
         }else{
@@ -84,41 +113,296 @@ impl CurrentUI{
 
     }
 
+    /// Opens a nested view on top of the current view, e.g. the detail view of the selected village.
+    /// The nested view receives the selection of the current view.
+    /// # Arguments
+    /// * `ui_type` is the type of the nested view
+    pub fn push_ui_type(&mut self, ui_type: UIType){
+
+        let selection = self.get_selection();
+
+        log::debug!(from:? = self.ui_type, to:? = ui_type, selection; "Opened nested view");
+
+        self.history.push(self.ui_type);
+        self.ui_type = ui_type;
+        self.expiration_time = None;
+        self.reset_scroll_offset();
+
+        if let Some(renderer) = self.get_current_ui_renderer_mut() {
+            renderer.set_parent_selection(selection);
+        }
+    }
+
+    /// Returns to the previous view in the navigation history, which keeps its scrolling offset.
+    /// Returns false, if there is no previous view.
+    pub fn go_back(&mut self) -> bool{
+
+        match self.history.pop() {
+            Some(ui_type) => {
+                log::debug!(from:? = self.ui_type, to:? = ui_type; "Went back");
+                self.ui_type = ui_type;
+                self.expiration_time = None;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the path of views from the root of the navigation history to the current view
+    pub fn get_path(&self) -> Vec<UIType>{
+        let mut path = self.history.clone();
+        path.push(self.ui_type);
+        path
+    }
+
     /// Increments or decrements the scrolling offset of the current view, depending on its
-    /// scrolling-direction.
+    /// scrolling-direction. The offset is bounded by the end of the view's content.
     /// # Arguments
-    /// `direction` - The scrolling direction, either up or down
+    /// `direction` - The scrolling direction, e.g. up, down or by a page
     pub fn scroll(&mut self, direction: ScrollingDirection){
-        let mut scroll_offset = self.get_current_ui_renderer_mut().get_scroll_offset();
+        if let Some(renderer) = self.get_current_ui_renderer_mut() {
+            let scroll_offset = renderer.get_scroll_offset();
+            let max_scroll_offset = renderer.get_max_scroll_offset();
+            let page_height = renderer.get_page_height();
 
-        match direction{
-            ScrollingDirection::Up => {
-                if scroll_offset > 0{
-                    scroll_offset -= 1
-                }
-            }
-            ScrollingDirection::Down => scroll_offset += 1,
+            let scroll_offset = match direction{
+                ScrollingDirection::Up => scroll_offset.saturating_sub(1),
+                ScrollingDirection::Down => scroll_offset.saturating_add(1),
+                ScrollingDirection::PageUp => scroll_offset.saturating_sub(page_height),
+                ScrollingDirection::PageDown => scroll_offset.saturating_add(page_height),
+                ScrollingDirection::Home => 0,
+                ScrollingDirection::End => max_scroll_offset,
+            };
+
+            // The content can't be scrolled beyond its end
+            renderer.set_scroll_offset(scroll_offset.min(max_scroll_offset));
         }
+    }
+
+    /// Returns the index of the entry, which is selected in the current view, e.g. the selected
+    /// neighbour in the diplomacy view.
+    pub fn get_selection(&self) -> usize{
+        self.renderer_for_ui_type.get(&self.ui_type)
+            .map_or(0, |renderer| renderer.get_selection())
+    }
+
+    /// Returns the areas, which the current view has covered in its last rendering
+    pub fn get_layout(&self) -> ViewLayout{
+        self.renderer_for_ui_type.get(&self.ui_type)
+            .map_or(ViewLayout::default(), |renderer| renderer.get_layout())
+    }
+
+    /// Selects the entry, which the current view displays in the given row of the terminal
+    /// # Arguments
+    /// * `row` is the row of the terminal
+    pub fn select_at(&mut self, row: u16){
+        if let Some(renderer) = self.get_current_ui_renderer_mut() {
+            renderer.select_at(row);
+        }
+    }
 
-        self.get_current_ui_renderer_mut().set_scroll_offset(scroll_offset);
+    /// Returns the text input of the current view, if it has one
+    pub fn text_input_mut(&mut self) -> Option<&mut TextInput>{
+        self.get_current_ui_renderer_mut()
+            .and_then(|renderer| renderer.text_input_mut())
+    }
+
+    /// Switches the current view to its next filter, if it can be filtered
+    pub fn cycle_filter(&mut self){
+        if let Some(renderer) = self.get_current_ui_renderer_mut() {
+            renderer.cycle_filter();
+        }
     }
 
     /// Resets the scroll offset to zero in order to ensure that a menu will be displayed from the
     /// very top onwards
     fn reset_scroll_offset(&mut self) {
-        self.get_current_ui_renderer_mut().set_scroll_offset(0);
+        if let Some(renderer) = self.get_current_ui_renderer_mut() {
+            renderer.set_scroll_offset(0);
+        }
     }
 
-    /// Draws the current ui to the terminal. The rendering is done by a struct, which implements the
-    /// TerminalRenderer-trait. For every UIType, there is exactly one such struct in the
+    /// Draws the current ui into a frame of the terminal. The rendering is done by a struct, which
+    /// implements the TerminalRenderer-trait. For every UIType, there is exactly one such struct in the
     /// renderer_for_ui_type.
-    pub fn render(&mut self, terminal: &mut Terminal<CrosstermBackend<Stdout>>, menu_widget : &List, game_state: Arc<RwLock<GameState>>) {
+    pub fn render(&mut self, frame: &mut Frame<B>, menu : &ViewRegistry, game_state: &GameState, theme: &Theme) {
+
+        if let Some(renderer) = self.get_current_ui_renderer_mut() {
+            renderer.render(frame,menu,game_state,theme);
+        }
+    }
 
-        self.get_current_ui_renderer_mut().render(terminal,menu_widget,game_state);
+    /// A helper method to return the TerminalRenderer-trait for the current view. There is no
+    /// renderer for UIType::Terminated, as nothing is drawn once the app terminates.
+    fn get_current_ui_renderer_mut(&mut self) -> Option<&mut Box<dyn TerminalRenderer<B>>>{
+        self.renderer_for_ui_type.get_mut(&self.ui_type)
     }
+}
+
+#[cfg(test)]
+mod tests {
 
-    /// A helper method to return the TerminalRenderer-trait for the current view
-    fn get_current_ui_renderer_mut(&mut self) -> &mut Box<dyn TerminalRenderer>{
-        self.renderer_for_ui_type.get_mut(&self.ui_type).unwrap()
+    use tui::backend::TestBackend;
+    use tui::Terminal;
+
+    use super::*;
+    use crate::data::saves::load_save_file;
+    use crate::game::messages::MessageCategory;
+
+    /// The height of the view's content within its borders in a terminal of 12 rows
+    const PAGE_HEIGHT: u16 = 10;
+
+    /// Renders the current view into the terminal, so that it knows the height of its content
+    /// # Arguments
+    /// * `cur_ui` is the current ui, whose view is rendered
+    /// * `terminal` is the terminal, into which the view is rendered
+    /// * `game_state` is the game state, which is displayed
+    fn render(cur_ui: &mut CurrentUI<TestBackend>, terminal: &mut Terminal<TestBackend>, game_state: &GameState) {
+        terminal.draw(|f| cur_ui.render(f, &ViewRegistry::new(), game_state, &Theme::default())).unwrap();
     }
-}
\ No newline at end of file
+
+    /// Returns the game state of the save file, whose message log contains the given number of messages
+    /// # Arguments
+    /// * `num_messages` is the number of messages
+    fn game_state(num_messages: usize) -> GameState {
+
+        let mut game_state = load_save_file("k_best_kingdom.json").unwrap();
+        game_state.message_log = Default::default();
+
+        for i in 0..num_messages {
+            game_state.post_message(MessageCategory::Economy, format!("Message {i}"));
+        }
+
+        game_state
+    }
+
+    /// Returns the current ui, which displays the given view, and a terminal of 12 rows
+    /// # Arguments
+    /// * `ui_type` is the type of the view
+    fn current_ui(ui_type: UIType) -> (CurrentUI<TestBackend>, Terminal<TestBackend>) {
+
+        let mut cur_ui = CurrentUI::new(vec![]);
+        cur_ui.change_ui_type(ui_type);
+
+        (cur_ui, Terminal::new(TestBackend::new(60, PAGE_HEIGHT + 2)).unwrap())
+    }
+
+    /// Returns the scrolling offset of the current view
+    /// # Arguments
+    /// * `cur_ui` is the current ui
+    fn scroll_offset(cur_ui: &mut CurrentUI<TestBackend>) -> u16 {
+        cur_ui.get_current_ui_renderer_mut().unwrap().get_scroll_offset()
+    }
+
+    #[test]
+    fn the_message_log_scrolls_between_its_start_and_its_end() {
+
+        let game_state = game_state(30);
+        let (mut cur_ui, mut terminal) = current_ui(UIType::MessageLog);
+        render(&mut cur_ui, &mut terminal, &game_state);
+
+        cur_ui.scroll(ScrollingDirection::Up);
+        cur_ui.scroll(ScrollingDirection::PageUp);
+        assert_eq!(scroll_offset(&mut cur_ui), 0);
+
+        cur_ui.scroll(ScrollingDirection::PageDown);
+        assert_eq!(scroll_offset(&mut cur_ui), PAGE_HEIGHT);
+
+        // The last message is displayed at the bottom at most
+        cur_ui.scroll(ScrollingDirection::PageDown);
+        assert_eq!(scroll_offset(&mut cur_ui), 30 - PAGE_HEIGHT);
+
+        cur_ui.scroll(ScrollingDirection::Down);
+        assert_eq!(scroll_offset(&mut cur_ui), 30 - PAGE_HEIGHT);
+
+        cur_ui.scroll(ScrollingDirection::Home);
+        cur_ui.scroll(ScrollingDirection::End);
+        assert_eq!(scroll_offset(&mut cur_ui), 30 - PAGE_HEIGHT);
+    }
+
+    #[test]
+    fn the_message_log_is_scrolled_back_when_it_shrinks_below_the_viewport() {
+
+        let (mut cur_ui, mut terminal) = current_ui(UIType::MessageLog);
+        render(&mut cur_ui, &mut terminal, &game_state(30));
+
+        cur_ui.scroll(ScrollingDirection::End);
+        render(&mut cur_ui, &mut terminal, &game_state(5));
+        assert_eq!(scroll_offset(&mut cur_ui), 0);
+
+        cur_ui.scroll(ScrollingDirection::Down);
+        cur_ui.scroll(ScrollingDirection::End);
+        assert_eq!(scroll_offset(&mut cur_ui), 0);
+    }
+
+    #[test]
+    fn the_selection_of_a_list_stays_within_its_entries() {
+
+        let mut game_state = game_state(0);
+        let num_villages = game_state.kingdom.villages.len() as u16;
+        let (mut cur_ui, mut terminal) = current_ui(UIType::Villages);
+        render(&mut cur_ui, &mut terminal, &game_state);
+
+        cur_ui.scroll(ScrollingDirection::Up);
+        assert_eq!(scroll_offset(&mut cur_ui), 0);
+
+        // A list may select its last entry, even though it fits into the view
+        cur_ui.scroll(ScrollingDirection::PageDown);
+        cur_ui.scroll(ScrollingDirection::Down);
+        assert_eq!(scroll_offset(&mut cur_ui), num_villages - 1);
+
+        // The selection moves to the last remaining entry
+        game_state.kingdom.villages.truncate(1);
+        render(&mut cur_ui, &mut terminal, &game_state);
+        assert_eq!(scroll_offset(&mut cur_ui), 0);
+
+        game_state.kingdom.villages.clear();
+        render(&mut cur_ui, &mut terminal, &game_state);
+        cur_ui.scroll(ScrollingDirection::End);
+        assert_eq!(scroll_offset(&mut cur_ui), 0);
+    }
+
+    #[test]
+    fn nested_views_are_pushed_onto_the_history_and_left_one_at_a_time() {
+
+        let game_state = game_state(0);
+        let (mut cur_ui, mut terminal) = current_ui(UIType::Villages);
+        render(&mut cur_ui, &mut terminal, &game_state);
+
+        cur_ui.scroll(ScrollingDirection::Down);
+        cur_ui.push_ui_type(UIType::VillageDetail);
+        cur_ui.push_ui_type(UIType::RenameVillage);
+        assert_eq!(cur_ui.get_path(), vec![UIType::Main, UIType::Villages, UIType::VillageDetail, UIType::RenameVillage]);
+
+        // Every nested view receives the selection of its parent, i.e. the second village
+        assert_eq!(cur_ui.get_selection(), 1);
+
+        assert!(cur_ui.go_back());
+        assert_eq!(cur_ui.get_path(), vec![UIType::Main, UIType::Villages, UIType::VillageDetail]);
+        assert_eq!(cur_ui.get_selection(), 1);
+
+        // The parent view keeps its selection
+        assert!(cur_ui.go_back());
+        assert_eq!(cur_ui.ui_type, UIType::Villages);
+        assert_eq!(scroll_offset(&mut cur_ui), 1);
+
+        assert!(cur_ui.go_back());
+        assert_eq!(cur_ui.get_path(), vec![UIType::Main]);
+        assert!(!cur_ui.go_back());
+        assert_eq!(cur_ui.ui_type, UIType::Main);
+    }
+
+    #[test]
+    fn changing_the_view_replaces_the_history() {
+
+        let (mut cur_ui, _) = current_ui(UIType::Villages);
+        cur_ui.push_ui_type(UIType::VillageDetail);
+
+        cur_ui.change_ui_type(UIType::Crafts);
+        assert_eq!(cur_ui.get_path(), vec![UIType::Main, UIType::Crafts]);
+
+        cur_ui.change_ui_type(UIType::Main);
+        assert_eq!(cur_ui.get_path(), vec![UIType::Main]);
+        assert!(!cur_ui.go_back());
+    }
+}
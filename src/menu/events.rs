@@ -14,6 +14,14 @@ pub struct UIEvent{
     timeout_ui: Option<UIType>,
 }
 
+impl UIEvent{
+
+    /// Returns the key input event, if this is no timeout-event
+    pub fn get_input_key_event(&self) -> Option<&Event>{
+        self.input_key_event.as_ref()
+    }
+}
+
 /// A builder-struct for the UIEvent.
 pub struct UIEventBuilder{
     /// A key input event
@@ -63,12 +71,7 @@ impl UIEventBuilder{
                     None => None,
                     Some(input_key_event) => Some(Clone::clone(input_key_event))
                 },
-                timeout_ui: {
-                    match self.timeout_ui{
-                        None => None,
-                        Some(timeout_ui) => Some(timeout_ui)
-                    }
-                }
+                timeout_ui: self.timeout_ui,
             }
         })
     }
@@ -0,0 +1,297 @@
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crossterm::event::Event;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{self, GameState};
+
+/// The first line of a replay file. It contains everything, which is needed to start the game in
+/// the same state as the recording did.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ReplayHeader<S> {
+    /// The width of the terminal, when the recording started
+    width: u16,
+    /// The height of the terminal, when the recording started
+    height: u16,
+    /// The speed of the game during the recording
+    speed: f64,
+    /// The game state, when the recording started
+    game_state: S,
+}
+
+/// A single input event of a replay file, which follows the header
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ReplayEntry {
+    /// The time in milliseconds since the recording started
+    time: u64,
+    /// The tick of the game, in which the event has been processed
+    tick: u64,
+    /// The input event
+    event: Event,
+}
+
+/// Records every input event, which the user-interface processes, into a replay file. Every event
+/// is written as a line of JSON at once, so that the recording survives a crash.
+pub struct InputRecorder {
+    /// The replay file
+    file: File,
+    /// The time, at which the recording started
+    start: Instant,
+}
+
+impl InputRecorder {
+
+    /// Creates a replay file, whose header contains the game state at the start of the recording
+    /// # Arguments
+    /// * `path` is the path of the replay file
+    /// * `game_state` is the game state at the start of the recording
+    /// * `speed` is the speed of the game
+    pub fn create(path: &Path, game_state: &GameState, speed: f64) -> Result<InputRecorder, String> {
+
+        let (width, height) = crossterm::terminal::size().unwrap_or_default();
+        let header = ReplayHeader { width, height, speed, game_state };
+
+        let mut file = File::create(path)
+            .map_err(|e| format!("Unable to create the replay file '{}': {e}", path.display()))?;
+
+        serde_json::to_string(&header)
+            .map_err(|e| e.to_string())
+            .and_then(|line| writeln!(file, "{line}").map_err(|e| e.to_string()))
+            .map_err(|e| format!("Unable to write the replay file '{}': {e}", path.display()))?;
+
+        log::info!(file:% = path.display(); "Started recording the input");
+
+        Ok(InputRecorder { file, start: Instant::now() })
+    }
+
+    /// Appends an input event to the replay file
+    /// # Arguments
+    /// * `tick` is the current tick of the game
+    /// * `event` is the input event, which is processed next
+    pub fn record(&mut self, tick: u64, event: &Event) -> Result<(), String> {
+
+        let entry = ReplayEntry { time: self.start.elapsed().as_millis() as u64, tick, event: event.clone() };
+
+        serde_json::to_string(&entry)
+            .map_err(|e| e.to_string())
+            .and_then(|line| writeln!(self.file, "{line}").map_err(|e| e.to_string()))
+    }
+}
+
+/// The next step of a replay
+pub enum ReplayStep {
+    /// The game must be advanced by a tick
+    Tick,
+    /// The input event must be processed
+    Input(Event),
+    /// Nothing is due yet
+    Wait,
+    /// Every event has been replayed
+    Finished,
+}
+
+/// A replay of recorded input events. While the replay runs, it also drives the ticks of the game,
+/// so that every event is processed in the same tick as during the recording.
+pub struct Replay {
+    /// The events, which have not been replayed yet, the next one first
+    entries: VecDeque<ReplayEntry>,
+    /// The time, at which the replay started
+    start: Instant,
+    /// The time between two ticks at the speed of the recording
+    tick_interval: Duration,
+    /// The time, at which the next tick is due
+    next_tick: Instant,
+}
+
+impl Replay {
+
+    /// Reads a replay file. Returns the replay and the game state, in which the recording started.
+    /// The speed of the recording must lie within the same range as the speed on the command line.
+    /// # Arguments
+    /// * `path` is the path of the replay file
+    pub fn load(path: &Path) -> Result<(Replay, GameState), String> {
+
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Unable to read the replay file '{}': {e}", path.display()))?;
+
+        let mut lines = contents.lines();
+
+        let header: ReplayHeader<GameState> = lines.next()
+            .ok_or_else(|| String::from("The replay file is empty"))
+            .and_then(|line| serde_json::from_str(line).map_err(|e| e.to_string()))
+            .map_err(|e| format!("Invalid header in the replay file '{}': {e}", path.display()))?;
+
+        let entries = lines.enumerate()
+            .map(|(i, line)| serde_json::from_str(line)
+                .map_err(|e| format!("Invalid event in line {} of the replay file '{}': {e}", i + 2, path.display())))
+            .collect::<Result<VecDeque<ReplayEntry>, String>>()?;
+
+        let tick_interval = game::tick_interval(header.speed)
+            .map_err(|e| format!("Invalid header in the replay file '{}': {e}", path.display()))?;
+
+        if (header.width, header.height) != crossterm::terminal::size().unwrap_or_default() {
+            log::warn!(width = header.width, height = header.height;
+                "The terminal's size differs from the recording, so mouse events may hit other views");
+        }
+
+        log::info!(file:% = path.display(), events = entries.len(); "Started replaying the input");
+
+        let start = Instant::now();
+
+        let replay = Replay { entries, start, tick_interval, next_tick: start + tick_interval };
+
+        Ok((replay, header.game_state))
+    }
+
+    /// Returns the next step of the replay. The game is advanced tick by tick at the speed of the
+    /// recording, until it reaches the tick of the next event. The event is due, as soon as the
+    /// time of the recording has passed.
+    /// # Arguments
+    /// * `tick` is the current tick of the game
+    pub fn next_step(&mut self, tick: u64) -> ReplayStep {
+
+        let Some(entry) = self.entries.front() else {
+            return ReplayStep::Finished;
+        };
+
+        let now = Instant::now();
+
+        if tick < entry.tick {
+            if now < self.next_tick {
+                return ReplayStep::Wait;
+            }
+            self.next_tick += self.tick_interval;
+            return ReplayStep::Tick;
+        }
+
+        if now < self.start + Duration::from_millis(entry.time) {
+            return ReplayStep::Wait;
+        }
+
+        // The ticks, which have been held back until the event was due, don't follow in a burst
+        self.next_tick = self.next_tick.max(now);
+
+        ReplayStep::Input(self.entries.pop_front().unwrap().event)
+    }
+
+    /// Returns the time, at which the next step of the replay is due, or None, if every event has
+    /// been replayed
+    /// # Arguments
+    /// * `tick` is the current tick of the game
+    pub fn next_step_time(&self, tick: u64) -> Option<Instant> {
+
+        let entry = self.entries.front()?;
+
+        if tick < entry.tick {
+            Some(self.next_tick)
+        } else {
+            Some(self.start + Duration::from_millis(entry.time))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::*;
+    use crate::data::saves::load_save_file;
+
+    /// Returns a key event of the given character
+    /// # Arguments
+    /// * `c` is the character of the key
+    fn key(c: char) -> Event {
+        Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+    }
+
+    /// Returns a replay of the given events, which have been recorded at the start of the recording
+    /// # Arguments
+    /// * `events` are the ticks and the events of the replay
+    /// * `tick_interval` is the time between two ticks
+    fn replay(events: Vec<(u64, Event)>, tick_interval: Duration) -> Replay {
+
+        let start = Instant::now();
+        let entries = events.into_iter()
+            .map(|(tick, event)| ReplayEntry { time: 0, tick, event })
+            .collect();
+
+        Replay { entries, start, tick_interval, next_tick: start }
+    }
+
+    #[test]
+    fn recorded_events_are_replayed_from_the_recorded_game_state() {
+
+        let path = std::env::temp_dir().join(format!("railan-replay-{}.jsonl", std::process::id()));
+        let game_state = load_save_file("k_best_kingdom.json").unwrap();
+        let events = vec![(0, key('v')), (3, Event::Resize(80, 24)), (3, key('q'))];
+
+        let mut recorder = InputRecorder::create(&path, &game_state, 2.0).unwrap();
+        for (tick, event) in &events {
+            recorder.record(*tick, event).unwrap();
+        }
+        drop(recorder);
+
+        let (replay, replayed_state) = Replay::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(serde_json::to_string(&replayed_state).unwrap(), serde_json::to_string(&game_state).unwrap());
+        assert_eq!(replay.tick_interval, game::TICK_DURATION.div_f64(2.0));
+
+        let replayed_events: Vec<(u64, Event)> = replay.entries.into_iter()
+            .map(|entry| (entry.tick, entry.event))
+            .collect();
+        assert_eq!(replayed_events, events);
+    }
+
+    #[test]
+    fn ticks_are_run_until_the_tick_of_the_next_event() {
+
+        let mut replay = replay(vec![(0, key('a')), (2, key('b'))], Duration::ZERO);
+
+        assert!(matches!(replay.next_step(0), ReplayStep::Input(event) if event == key('a')));
+        assert!(matches!(replay.next_step(0), ReplayStep::Tick));
+        assert!(matches!(replay.next_step(1), ReplayStep::Tick));
+        assert!(matches!(replay.next_step(2), ReplayStep::Input(event) if event == key('b')));
+        assert!(matches!(replay.next_step(2), ReplayStep::Finished));
+        assert_eq!(replay.next_step_time(2), None);
+    }
+
+    #[test]
+    fn steps_wait_until_they_are_due() {
+
+        let mut replay = replay(vec![(1, key('a'))], Duration::from_secs(3600));
+
+        assert!(matches!(replay.next_step(0), ReplayStep::Tick));
+        assert!(matches!(replay.next_step(0), ReplayStep::Wait));
+        assert_eq!(replay.next_step_time(0), Some(replay.start + Duration::from_secs(3600)));
+
+        replay.entries[0].time = 3_600_000;
+
+        assert!(matches!(replay.next_step(1), ReplayStep::Wait));
+        assert_eq!(replay.next_step_time(1), Some(replay.start + Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn replays_with_an_invalid_speed_are_rejected() {
+
+        let game_state = load_save_file("k_best_kingdom.json").unwrap();
+
+        for (i, speed) in [0.0, -1.0, 1e-300, 1e300].into_iter().enumerate() {
+
+            let path = std::env::temp_dir().join(format!("railan-replay-{}-speed-{i}.jsonl", std::process::id()));
+            drop(InputRecorder::create(&path, &game_state, speed).unwrap());
+
+            let result = Replay::load(&path);
+            fs::remove_file(&path).unwrap();
+
+            assert!(result.is_err(), "speed {speed}");
+        }
+    }
+}
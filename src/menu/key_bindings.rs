@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+use std::fs;
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+
+use crate::menu::assets;
+use crate::menu::events::{UIEvent, UIEventBuilder};
+use crate::menu::ui_foundations::{ActionType, UIError, UIType};
+
+/// The name of the key bindings file, both in the asset-folder and the user's config directory
+pub const KEY_BINDINGS_FILE: &str = "keys.json";
+
+/// A single entry of the key bindings file
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct KeyBindingEntry {
+    /// A key chord, e.g. "v", "Up" or "Ctrl+s"
+    key: String,
+    /// The action, which is executed if the key chord is pressed
+    action: ActionType,
+}
+
+/// A key chord, which is bound to an action
+pub struct KeyBinding {
+    /// The key chord as written in the key bindings file, which is also used as its label
+    pub chord: String,
+    /// The key event, which corresponds to the key chord
+    pub key_event: KeyEvent,
+    /// The action, which is executed if the key chord is pressed
+    pub action: ActionType,
+}
+
+/// The set of key bindings, which is used by the user-interface. They are read from the file
+/// 'keys.json', either from the user's config directory or, if there is none, from the asset-folder.
+pub struct KeyBindings {
+    /// Every key binding in the order of the key bindings file
+    bindings: Vec<KeyBinding>,
+}
+
+impl KeyBindings {
+
+    /// Loads the key bindings. A file 'railan/keys.json' in the user's config directory, that is
+    /// $XDG_CONFIG_HOME or $HOME/.config, takes precedence over the one in the asset-folder.
+    pub fn load() -> Result<KeyBindings, UIError> {
+
+        let json = match assets::user_config_path(KEY_BINDINGS_FILE).filter(|p| p.is_file()) {
+            Some(path) => fs::read_to_string(&path)
+                .map_err(|e| UIError::InvalidKeyBindings(format!("Unable to read '{}': {e}", path.display())))?,
+            None => assets::read_asset(KEY_BINDINGS_FILE).map_err(UIError::Asset)?,
+        };
+
+        KeyBindings::parse(&json)
+    }
+
+    /// Parses and validates the JSON-content of a key bindings file. The bindings are invalid, if
+    /// a key chord cannot be parsed, is bound more than once or is bound to an action, which cannot
+    /// be triggered by a key.
+    /// # Arguments
+    /// * `json` is the content of the key bindings file
+    pub fn parse(json: &str) -> Result<KeyBindings, UIError> {
+
+        let entries: Vec<KeyBindingEntry> = serde_json::from_str(json)
+            .map_err(|e| UIError::InvalidKeyBindings(format!("The file is not well-formatted: {e}")))?;
+
+        let mut bindings: Vec<KeyBinding> = Vec::new();
+
+        for entry in entries {
+
+            let key_event = parse_key_chord(&entry.key)?;
+
+            match entry.action {
+                ActionType::HandleUITimeout(_) => {
+                    return Err(UIError::InvalidKeyBindings(format!("'{}' cannot be bound to a timeout", entry.key)));
+                }
+                ActionType::ChangeView(ui_type) if !ui_type.is_directly_accessible() => {
+                    return Err(UIError::InvalidKeyBindings(format!("'{}' cannot be bound to the view {:?}", entry.key, entry.action)));
+                }
+                _ => {}
+            }
+
+            // KeyEvents compare equal regardless of how the case of a character was written,
+            // e.g. "V" and "Shift+v" conflict.
+            if let Some(binding) = bindings.iter().find(|b| b.key_event == key_event) {
+                return Err(UIError::InvalidKeyBindings(format!(
+                    "'{}' and '{}' are the same key, but bound to {:?} and {:?}",
+                    binding.chord, entry.key, binding.action, entry.action
+                )));
+            }
+
+            bindings.push(KeyBinding { chord: entry.key, key_event, action: entry.action });
+        }
+
+        Ok(KeyBindings { bindings })
+    }
+
+    /// Returns a map from the ui-event of every key chord to its action
+    pub fn to_event_map(&self) -> HashMap<UIEvent, ActionType> {
+        self.bindings.iter()
+            .map(|b| (UIEventBuilder::new().input_key_event(Event::Key(b.key_event)).build().unwrap(), b.action))
+            .collect()
+    }
+
+    /// Returns every view, which is bound to a key chord, together with the chord's label in the
+    /// order of the key bindings file. If a view is bound to several chords, only the first one is
+    /// returned.
+    pub fn view_hotkeys(&self) -> Vec<(&str, UIType)> {
+
+        let mut hotkeys: Vec<(&str, UIType)> = Vec::new();
+
+        for binding in &self.bindings {
+            if let ActionType::ChangeView(ui_type) = binding.action {
+                if !hotkeys.iter().any(|(_, t)| *t == ui_type) {
+                    hotkeys.push((binding.chord.as_str(), ui_type));
+                }
+            }
+        }
+
+        hotkeys
+    }
+}
+
+/// Returns a label for a key event, which can be parsed as key chord again, e.g. "Ctrl+s"
+/// # Arguments
+/// * `key_event` is the key event, for which a label is returned
+pub fn key_label(key_event: &KeyEvent) -> String {
+
+    let mut label = String::new();
+
+    if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+        label.push_str("Ctrl+");
+    }
+    if key_event.modifiers.contains(KeyModifiers::ALT) {
+        label.push_str("Alt+");
+    }
+    // Upper case characters already imply the shift-key
+    if key_event.modifiers.contains(KeyModifiers::SHIFT) && !matches!(key_event.code, KeyCode::Char(_)) {
+        label.push_str("Shift+");
+    }
+
+    match key_event.code {
+        KeyCode::Char(' ') => label.push_str("Space"),
+        KeyCode::Char(c) if key_event.modifiers.contains(KeyModifiers::SHIFT) => label.push(c.to_ascii_uppercase()),
+        KeyCode::Char(c) => label.push(c),
+        KeyCode::F(n) => label.push_str(&format!("F{n}")),
+        KeyCode::BackTab => label.push_str("Tab"),
+        // The names of the remaining keys match their debug-representation, e.g. "PageUp"
+        code => label.push_str(&format!("{code:?}")),
+    }
+
+    label
+}
+
+/// Parses a key chord, which consists of any number of modifiers and a key separated by '+',
+/// e.g. "q", "PageDown" or "Ctrl+Shift+s". Keys are either single characters or the names of
+/// special keys.
+/// # Arguments
+/// * `chord` is the key chord, which should be parsed
+fn parse_key_chord(chord: &str) -> Result<KeyEvent, UIError> {
+
+    let invalid = |reason: &str| UIError::InvalidKeyBindings(format!("'{chord}' {reason}"));
+
+    // A single '+' is a key rather than a separator
+    let (modifiers, key) = match chord.strip_suffix("++") {
+        Some(modifiers) => (modifiers, "+"),
+        None => match chord.rsplit_once('+') {
+            Some((modifiers, key)) if !key.is_empty() => (modifiers, key),
+            _ => ("", chord),
+        },
+    };
+
+    let mut key_modifiers = KeyModifiers::NONE;
+
+    for modifier in modifiers.split('+').filter(|m| !m.is_empty()) {
+        key_modifiers |= match modifier.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => KeyModifiers::CONTROL,
+            "alt" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => return Err(invalid("contains an unknown modifier")),
+        };
+    }
+
+    let mut chars = key.chars();
+
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) => KeyCode::Char(c),
+        _ => match key.to_ascii_lowercase().as_str() {
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "enter" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            "delete" => KeyCode::Delete,
+            "insert" => KeyCode::Insert,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "space" => KeyCode::Char(' '),
+            function_key => function_key.strip_prefix('f')
+                .and_then(|n| n.parse::<u8>().ok())
+                .filter(|n| (1..=12).contains(n))
+                .map(KeyCode::F)
+                .ok_or_else(|| invalid("contains an unknown key"))?,
+        },
+    };
+
+    // Terminals report the tab-key together with the shift-key as back-tab
+    let code = match code {
+        KeyCode::Tab if key_modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+        code => code,
+    };
+
+    Ok(KeyEvent::new(code, key_modifiers))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /// Returns the message of the error, which parsing the given key bindings leads to
+    /// # Arguments
+    /// * `json` is the content of a key bindings file
+    fn parse_error(json: &str) -> String {
+        match KeyBindings::parse(json) {
+            Ok(_) => panic!("The key bindings should be invalid"),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    #[test]
+    fn key_chords_with_modifiers_are_parsed() {
+
+        assert_eq!(parse_key_chord("Ctrl+c").unwrap(), KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
+        assert_eq!(parse_key_chord("Shift+Tab").unwrap(), KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT));
+        assert_eq!(parse_key_chord("ctrl+alt+Delete").unwrap(), KeyEvent::new(KeyCode::Delete, KeyModifiers::CONTROL | KeyModifiers::ALT));
+        assert_eq!(parse_key_chord("Ctrl++").unwrap(), KeyEvent::new(KeyCode::Char('+'), KeyModifiers::CONTROL));
+        assert_eq!(parse_key_chord("F5").unwrap(), KeyEvent::new(KeyCode::F(5), KeyModifiers::NONE));
+        assert_eq!(parse_key_chord("V").unwrap(), parse_key_chord("Shift+v").unwrap());
+    }
+
+    #[test]
+    fn labels_are_parsed_as_the_same_key_chord() {
+
+        for chord in ["q", "Ctrl+c", "Shift+Tab", "Alt+Space", "PageDown", "F12", "?", "Ctrl++"] {
+            let key_event = parse_key_chord(chord).unwrap();
+            assert_eq!(parse_key_chord(&key_label(&key_event)).unwrap(), key_event, "{chord}");
+        }
+    }
+
+    #[test]
+    fn unknown_keys_and_modifiers_are_rejected() {
+
+        for chord in ["Hyper", "F13", "Ctrl+", "Meta+x", "Ctrl+PgUp"] {
+            assert!(parse_key_chord(chord).is_err(), "{chord}");
+        }
+
+        let error = parse_error(r#"[{ "Key": "Ctrl+Hyper", "Action": "Save" }]"#);
+        assert_eq!(error, "Invalid key bindings: 'Ctrl+Hyper' contains an unknown key");
+    }
+
+    #[test]
+    fn duplicate_bindings_are_reported() {
+
+        let error = parse_error(r#"[
+            { "Key": "V", "Action": { "ChangeView": "Villages" } },
+            { "Key": "Shift+v", "Action": "Save" }
+        ]"#);
+
+        assert_eq!(error, "Invalid key bindings: 'V' and 'Shift+v' are the same key, but bound to ChangeView(Villages) and Save");
+    }
+
+    #[test]
+    fn the_default_key_bindings_are_valid() {
+
+        let key_bindings = KeyBindings::parse(&assets::read_asset(KEY_BINDINGS_FILE).unwrap()).unwrap();
+
+        assert!(key_bindings.view_hotkeys().contains(&("q", UIType::Quit)));
+    }
+}
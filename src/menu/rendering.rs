@@ -1,29 +1,55 @@
 pub mod diplomacy_menu;
+pub mod dialog;
+pub mod error_banner;
 pub mod full_screen;
+pub mod help_overlay;
 pub mod craft_menu;
 pub mod main_menu;
+pub mod message_log_menu;
+pub mod rename_village_menu;
+pub mod scrollbar;
+pub mod settings_menu;
+pub mod size_warning;
+pub mod statistics_menu;
+pub mod text_input;
+pub mod toasts;
+pub mod village_detail_menu;
 pub mod village_menu;
 
 use tui::{
-    backend::CrosstermBackend,
-    widgets::List,
-    Terminal,
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    Frame,
 };
-use std::io::Stdout;
-use std::sync::{Arc,RwLock};
 use crate::game::GameState;
+use crate::menu::view_registry::ViewRegistry;
+use crate::menu::rendering::text_input::TextInput;
+use crate::menu::themes::Theme;
+
+/// The areas of the frame, which a view has covered in its last rendering. They are used to
+/// hit-test mouse events. Views without a menu, e.g. full screen messages, cover no areas.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct ViewLayout{
+    /// The area of the menu
+    pub menu: Rect,
+    /// The area of the view's content
+    pub content: Rect,
+}
 
 /// A trait, which is used to render views in the terminal. For every type of view there will be
-/// a corresponding struct, which implements this trait.
-pub trait TerminalRenderer{
+/// a corresponding struct, which implements this trait. The views are drawn into frames of any
+/// backend, so that they can also be rendered into the buffer of a TestBackend.
+pub trait TerminalRenderer<B: Backend>{
 
-    /// Renders a view to the terminal.
+    /// Renders a view into a frame of the terminal. Overlays, e.g. dialogs, may be drawn into the
+    /// same frame afterwards.
     /// # Arguments
-    /// * `terminal` is the terminal in which the rendering shall be done
-    /// * `menu_widget` is the crossterm-menu widget, which must be included in the rendering
+    /// * `frame` is the terminal's frame in which the rendering shall be done
+    /// * `menu` is the registry of views, whose menu must be included in the rendering
     /// * `game_state` contains the player's game-data, which will in some way be used in the rendering
-    /// for most widgets.
-    fn render(&mut self, terminal: &mut Terminal<CrosstermBackend<Stdout>>, menu_widget : &List, game_state: Arc<RwLock<GameState>>);
+    ///   for most widgets.
+    /// * `theme` is the theme, whose colors are used
+    fn render(&mut self, frame: &mut Frame<B>, menu : &ViewRegistry, game_state: &GameState, theme: &Theme);
 
     /// Every struct, which implements the TerminalRenderer trait has to implement the following two
     /// methods, which allow every menu to implement scrolling functionality
@@ -34,4 +60,445 @@ pub trait TerminalRenderer{
     /// Similar to the setter, this method will return some scrolling offset. In case the menu does
     /// not allow any scrolling, it will be 0.
     fn get_scroll_offset(&self) -> u16;
-}
\ No newline at end of file
+
+    /// Returns the greatest scrolling offset, at which the end of the content is reached. It
+    /// follows from the height of the content in the last rendering. Views without any scrolling
+    /// return 0.
+    fn get_max_scroll_offset(&self) -> u16 {
+        0
+    }
+
+    /// Returns the number of lines, which the view scrolls at once by a page. By default, it is the
+    /// height of the content's area within its borders.
+    fn get_page_height(&self) -> u16 {
+        page_height(self.get_layout().content)
+    }
+
+    /// Views, which can be filtered, switch to their next filter. Every other view ignores this.
+    fn cycle_filter(&mut self) {
+        // nothing to do
+    }
+
+    /// Returns the index of the selected entry, which is passed on to a nested view, when it is
+    /// opened. By default, the scrolling offset of list views equals the selected entry.
+    fn get_selection(&self) -> usize {
+        self.get_scroll_offset() as usize
+    }
+
+    /// Nested views receive the selection of their parent view, when they are opened, e.g. the
+    /// detail view of a village receives the index of the village. Every other view ignores this.
+    /// # Arguments
+    /// * `_selection` is the index of the entry, which is selected in the parent view
+    fn set_parent_selection(&mut self, _selection: usize) {
+        // nothing to do
+    }
+
+    /// Views with a text input return it, so that typed characters can be passed to it
+    fn text_input_mut(&mut self) -> Option<&mut TextInput> {
+        None
+    }
+
+    /// Returns the areas, which the view has covered in its last rendering
+    fn get_layout(&self) -> ViewLayout {
+        ViewLayout::default()
+    }
+
+    /// List views select the entry, which is displayed in the given row of the terminal, e.g. after
+    /// a mouse click. Every other view ignores this.
+    /// # Arguments
+    /// * `_row` is the row of the terminal
+    fn select_at(&mut self, _row: u16) {
+        // nothing to do
+    }
+}
+
+/// The share of the terminal's width in percent, which the menu covers next to every view
+const MENU_WIDTH_PERCENTAGE: u32 = 15;
+
+/// Splits an area into the menu on the left and the view's content on the right. The menu covers a
+/// fixed share of the width, but at least as much as its widest entry needs.
+/// # Arguments
+/// * `area` is the area, which is split
+/// * `menu` is the registry of views, whose menu is displayed on the left
+pub fn split_view(area: Rect, menu: &ViewRegistry) -> Vec<Rect> {
+
+    let menu_width = (area.width as u32 * MENU_WIDTH_PERCENTAGE / 100) as u16;
+    let menu_width = menu_width.max(menu.get_min_width()).min(area.width);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(menu_width), Constraint::Min(0)].as_ref())
+        .split(area)
+}
+
+/// Returns the number of lines, which a bordered view displays at once, but at least one
+/// # Arguments
+/// * `area` is the area of the view including its borders
+pub fn page_height(area: Rect) -> u16 {
+    area.height.saturating_sub(2).max(1)
+}
+
+/// Returns true, if the given cell of the terminal lies within the area
+/// # Arguments
+/// * `area` is the area, which is tested
+/// * `column` is the column of the cell
+/// * `row` is the row of the cell
+pub fn rect_contains(area: Rect, column: u16, row: u16) -> bool {
+    column >= area.x && column < area.right() && row >= area.y && row < area.bottom()
+}
+
+/// Returns the index of the entry, which a bordered list displays in the given row of the terminal.
+/// If the row shows no entry, None is returned. Since a list is drawn from its top, until the
+/// selected entry has to be scrolled into view, the first displayed entry follows from the selection.
+/// # Arguments
+/// * `area` is the area of the list including its borders
+/// * `row` is the row of the terminal
+/// * `selected` is the index of the selected entry
+/// * `len` is the number of entries
+pub fn list_index_at(area: Rect, row: u16, selected: usize, len: usize) -> Option<usize> {
+
+    let inner_top = area.y + 1;
+    let inner_height = area.height.saturating_sub(2);
+
+    if row < inner_top || row >= inner_top + inner_height {
+        return None;
+    }
+
+    let offset = selected.saturating_sub(inner_height.saturating_sub(1) as usize);
+    let index = offset + (row - inner_top) as usize;
+
+    (index < len).then_some(index)
+}
+
+/// Returns a rectangle, which is centered within the given area
+/// # Arguments
+/// * `percent_x` is the width of the rectangle in percent of the area's width
+/// * `percent_y` is the height of the rectangle in percent of the area's height
+/// * `area` is the area, in which the rectangle is centered
+pub fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ].as_ref())
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ].as_ref())
+        .split(vertical[1])[1]
+}
+
+#[cfg(test)]
+mod tests {
+
+    use tui::{
+        backend::TestBackend,
+        buffer::Buffer,
+        style::Style,
+        widgets::{Block, Borders, List, ListItem, ListState},
+        Terminal,
+    };
+
+    use super::*;
+    use crate::menu::assets;
+    use crate::menu::key_bindings::{KeyBindings, KEY_BINDINGS_FILE};
+    use crate::game::messages::MessageCategory;
+    use crate::menu::rendering::craft_menu::CraftMenu;
+    use crate::menu::rendering::dialog::Dialog;
+    use crate::menu::rendering::message_log_menu::MessageLogMenu;
+    use crate::menu::rendering::statistics_menu::StatisticsMenu;
+    use crate::menu::rendering::village_menu::VillageMenu;
+    use crate::menu::ui_foundations::UIType;
+    use crate::menu::view_registry::ViewEntry;
+
+    /// The area of a list, whose entries are displayed in the rows 3 to 6
+    const LIST_AREA: Rect = Rect { x: 0, y: 2, width: 20, height: 6 };
+
+    #[test]
+    fn clicks_on_the_border_hit_no_entry() {
+
+        assert_eq!(list_index_at(LIST_AREA, 2, 0, 10), None);
+        assert_eq!(list_index_at(LIST_AREA, 7, 0, 10), None);
+        assert_eq!(list_index_at(LIST_AREA, 0, 0, 10), None);
+        assert_eq!(list_index_at(LIST_AREA, 3, 0, 10), Some(0));
+        assert_eq!(list_index_at(LIST_AREA, 6, 0, 10), Some(3));
+    }
+
+    #[test]
+    fn clicks_beyond_the_last_entry_hit_no_entry() {
+
+        assert_eq!(list_index_at(LIST_AREA, 4, 1, 2), Some(1));
+        assert_eq!(list_index_at(LIST_AREA, 5, 1, 2), None);
+        assert_eq!(list_index_at(LIST_AREA, 3, 0, 0), None);
+    }
+
+    #[test]
+    fn clicks_hit_the_entries_of_a_scrolled_list() {
+
+        // The selected entry is displayed in the last row, once it has to be scrolled into view
+        assert_eq!(list_index_at(LIST_AREA, 3, 9, 20), Some(6));
+        assert_eq!(list_index_at(LIST_AREA, 6, 9, 20), Some(9));
+        assert_eq!(list_index_at(LIST_AREA, 3, 3, 20), Some(0));
+        assert_eq!(list_index_at(LIST_AREA, 3, 4, 20), Some(1));
+    }
+
+    #[test]
+    fn clicks_hit_the_entry_which_is_drawn_in_the_row() {
+
+        let mut terminal = Terminal::new(TestBackend::new(20, 10)).unwrap();
+
+        for selected in [0, 5, 9, 19] {
+
+            let items: Vec<ListItem> = (0..20).map(|i| ListItem::new(i.to_string())).collect();
+            let list = List::new(items).block(Block::default().borders(Borders::ALL));
+            let mut state = ListState::default();
+            state.select(Some(selected));
+
+            terminal.draw(|f| f.render_stateful_widget(list, LIST_AREA, &mut state)).unwrap();
+
+            let buffer = terminal.backend().buffer();
+
+            for row in LIST_AREA.top() + 1..LIST_AREA.bottom() - 1 {
+
+                let line: String = (1..LIST_AREA.width - 1).map(|x| buffer.get(x, row).symbol.as_str()).collect();
+                let index = list_index_at(LIST_AREA, row, selected, 20);
+
+                assert_eq!(index.map(|i| i.to_string()), Some(line.trim().to_string()), "selected {selected}, row {row}");
+            }
+        }
+    }
+
+    /// The kingdom, which every snapshot displays
+    const KINGDOM: &str = r#"{
+        "Name": "Test Kingdom",
+        "Treasury": 100,
+        "Villages": [{ "Name": "Füchtorf", "NumCitizen": 123 }, { "Name": "Sassenberg", "NumCitizen": 321 }],
+        "Crafts": {
+            "Woodworker": [{ "CraftType": "Woodworker", "Lvl": 1 }, { "CraftType": "Woodworker", "Lvl": 23 }],
+            "Stonemason": [{ "CraftType": "Stonemason", "Lvl": 13 }]
+        }
+    }"#;
+
+    /// Returns the game state, which every snapshot displays
+    fn game_state() -> GameState {
+        serde_json::from_str(KINGDOM).unwrap()
+    }
+
+    /// Returns the menu of the default key bindings, in which the given view is the current one
+    /// # Arguments
+    /// * `ui_type` is the type of the current view
+    fn menu(ui_type: UIType) -> ViewRegistry {
+
+        let key_bindings = KeyBindings::parse(&assets::read_asset(KEY_BINDINGS_FILE).unwrap()).unwrap();
+        let mut views = ViewRegistry::new();
+
+        for (hotkey, ui_type) in key_bindings.view_hotkeys() {
+            views.register(ViewEntry { title: ui_type.title(), hotkey: String::from(hotkey), ui_type });
+        }
+
+        views.select(&[UIType::Main, ui_type]);
+        views
+    }
+
+    /// Renders a view and a dialog on top of it into the terminal and returns the drawn buffer. As
+    /// the snapshots only cover the layout and the text, the colors are removed.
+    /// # Arguments
+    /// * `terminal` is the terminal, into which the view is rendered
+    /// * `renderer` is the view
+    /// * `ui_type` is the type of the view
+    /// * `game_state` is the game state, which is displayed
+    /// * `dialog` is the dialog, which is drawn on top of the view
+    fn draw(terminal: &mut Terminal<TestBackend>, renderer: &mut dyn TerminalRenderer<TestBackend>, ui_type: UIType, game_state: &GameState, dialog: Option<&Dialog>) -> Buffer {
+
+        let menu = menu(ui_type);
+        let theme = Theme::default();
+
+        terminal.draw(|f| {
+            renderer.render(f, &menu, game_state, &theme);
+            if let Some(dialog) = dialog {
+                dialog.render(f, &theme);
+            }
+        }).unwrap();
+
+        let mut buffer = terminal.backend().buffer().clone();
+        buffer.set_style(buffer.area, Style::reset());
+        buffer
+    }
+
+    /// Returns the game state, whose statistics have been recorded for 20 days, in which the
+    /// treasury and the first village have grown
+    fn game_state_with_statistics() -> GameState {
+
+        let mut game_state = game_state();
+
+        for tick in 1..=20 {
+            game_state.kingdom.add_treasury(tick as i32);
+            game_state.kingdom.add_citizen(0, 1);
+            game_state.statistics.record(tick, &game_state.kingdom);
+        }
+
+        game_state
+    }
+
+    #[test]
+    fn the_village_view_is_drawn() {
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 8)).unwrap();
+
+        assert_eq!(draw(&mut terminal, &mut VillageMenu::new(), UIType::Villages, &game_state(), None), Buffer::with_lines(vec![
+            "┌Menu──────────┐┌Main > Villages───────────────────────────┐",
+            "│[v] Villages  ││> Füchtorf: 123 Citizen                   │",
+            "│[c] Crafts    ││  Sassenberg: 321 Citizen                 │",
+            "│[d] Diplomacy ││                                          │",
+            "│[l] Messages  ││                                          │",
+            "│[t] Statistics││                                          │",
+            "│[o] Settings  ││                                          │",
+            "└──────────────┘└──────────────────────────────────────────┘",
+        ]));
+    }
+
+    #[test]
+    fn the_village_view_is_drawn_anew_after_a_resize() {
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 8)).unwrap();
+        let mut village_menu = VillageMenu::new();
+        draw(&mut terminal, &mut village_menu, UIType::Villages, &game_state(), None);
+
+        terminal.backend_mut().resize(40, 6);
+
+        assert_eq!(draw(&mut terminal, &mut village_menu, UIType::Villages, &game_state(), None), Buffer::with_lines(vec![
+            "┌Menu──────────┐┌Main > Villages───────┐",
+            "│[v] Villages  ││> Füchtorf: 123 Citize│",
+            "│[c] Crafts    ││  Sassenberg: 321 Citi│",
+            "│[d] Diplomacy ││                      │",
+            "│[l] Messages  ││                      │",
+            "└──────────────┘└──────────────────────┘",
+        ]));
+    }
+
+    #[test]
+    fn the_craft_view_is_drawn() {
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 10)).unwrap();
+
+        assert_eq!(draw(&mut terminal, &mut CraftMenu::new(), UIType::Crafts, &game_state(), None), Buffer::with_lines(vec![
+            "┌Menu──────────┐┌Main > Crafts─────────────────────────────┐",
+            "│[v] Villages  ││                Woodworker:               │",
+            "│[c] Crafts    ││            Woodworker: Level 1           │",
+            "│[d] Diplomacy ││           Woodworker: Level 23           │",
+            "│[l] Messages  ││                Stonemason:               │",
+            "│[t] Statistics││           Stonemason: Level 13           │",
+            "│[o] Settings  ││                                          │",
+            "│[q] Quit      ││                                          │",
+            "│              ││                                          │",
+            "└──────────────┘└──────────────────────────────────────────┘",
+        ]));
+    }
+
+    #[test]
+    fn the_event_dialog_is_drawn_on_top_of_the_view() {
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 16)).unwrap();
+        let dialog = Dialog::new(
+            String::from("Fire"),
+            String::from("A fire breaks out in Füchtorf."),
+            vec![String::from("Extinguish"), String::from("Flee")],
+        );
+
+        assert_eq!(draw(&mut terminal, &mut VillageMenu::new(), UIType::Villages, &game_state(), Some(&dialog)), Buffer::with_lines(vec![
+            "┌Menu──────────┐┌Main > Villages───────────────────────────┐",
+            "│[v] Villages  ││> Füchtorf: 123 Citizen                   │",
+            "│[c] Crafts    ││  Sassenberg: 321 Citizen                 │",
+            "│[d] Diplomacy ││                                          │",
+            "│[l] Message┌Fire──────────────────────────────┐           │",
+            "│[t] Statist│  A fire breaks out in Füchtorf.  │           │",
+            "│[o] Setting│                                  │           │",
+            "│[q] Quit   │                                  │           │",
+            "│           │                                  │           │",
+            "│           │> Extinguish                      │           │",
+            "│           │  Flee                            │           │",
+            "│           └──────────────────────────────────┘           │",
+            "│              ││                                          │",
+            "│              ││                                          │",
+            "│              ││                                          │",
+            "└──────────────┘└──────────────────────────────────────────┘",
+        ]));
+    }
+
+    #[test]
+    fn the_statistics_view_is_drawn() {
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+
+        assert_eq!(draw(&mut terminal, &mut StatisticsMenu::new(), UIType::Statistics, &game_state_with_statistics(), None), Buffer::with_lines(vec![
+            "┌Menu──────────┐┌Main > Statistics (Last 30 days) - [F]ilter───────────────────┐",
+            "│[v] Villages  ││┌Treasury: 310─────┐┌Citizens: 464─────┐ ┌Craft levels: 37───┐│",
+            "│[c] Crafts    │││        ▃█        ││       ▂▅█        │ │                   ││",
+            "│[d] Diplomacy │││      ▃▇██        ││    ▃▅████        │ │                   ││",
+            "│[l] Messages  │││ ▁▂▄▆█████        ││▁▃▆███████        │ │                   ││",
+            "│[t] Statistics││└──────────────────┘└──────────────────┘ └───────────────────┘│",
+            "│[o] Settings  ││┌Statistic─────────────────────┐┌Treasury: 310───────────────┐│",
+            "│[q] Quit      │││> Treasury                    ││310 │                     ⡠⠊││",
+            "│              │││  Citizens of Füchtorf        ││    │                   ⢀⠜  ││",
+            "│              │││  Citizens of Sassenberg      ││    │                  ⡔⠁   ││",
+            "│              │││  Woodworker levels           ││    │                ⢀⠎     ││",
+            "│              │││  Stonemason levels           ││    │              ⡠⠊⠁      ││",
+            "│              │││                              ││    │            ⣀⠜         ││",
+            "│              │││                              ││    │         ⡠⠔⠊           ││",
+            "│              │││                              ││    │     ⢀⡠⠤⠊              ││",
+            "│              │││                              ││101 │⣀⠤⠤⠒⠊⠁                 ││",
+            "│              │││                              ││    └───────────────────────││",
+            "│              │││                              ││Day 1                 Day 20││",
+            "│              ││└──────────────────────────────┘└────────────────────────────┘│",
+            "└──────────────┘└──────────────────────────────────────────────────────────────┘",
+        ]));
+    }
+
+    #[test]
+    fn the_message_log_is_filtered_by_category() {
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 6)).unwrap();
+        let mut message_log_menu = MessageLogMenu::new();
+        let mut game_state = game_state();
+        game_state.post_message(MessageCategory::Economy, String::from("The harvest was good."));
+        game_state.post_message(MessageCategory::Diplomacy, String::from("Warendorf sends a gift."));
+        game_state.post_message(MessageCategory::Economy, String::from("Taxes were paid."));
+
+        let all = Buffer::with_lines(vec![
+            "┌Menu──────────┐┌Main > Messages (All) - [F]ilter──────────┐",
+            "│[v] Villages  ││Day 0 [Economy] Taxes were paid.          │",
+            "│[c] Crafts    ││Day 0 [Diplomacy] Warendorf sends a gift. │",
+            "│[d] Diplomacy ││Day 0 [Economy] The harvest was good.     │",
+            "│[l] Messages  ││                                          │",
+            "└──────────────┘└──────────────────────────────────────────┘",
+        ]);
+
+        assert_eq!(draw(&mut terminal, &mut message_log_menu, UIType::MessageLog, &game_state, None), all);
+
+        TerminalRenderer::<TestBackend>::cycle_filter(&mut message_log_menu);
+
+        assert_eq!(draw(&mut terminal, &mut message_log_menu, UIType::MessageLog, &game_state, None), Buffer::with_lines(vec![
+            "┌Menu──────────┐┌Main > Messages (Economy) - [F]ilter──────┐",
+            "│[v] Villages  ││Day 0 [Economy] Taxes were paid.          │",
+            "│[c] Crafts    ││Day 0 [Economy] The harvest was good.     │",
+            "│[d] Diplomacy ││                                          │",
+            "│[l] Messages  ││                                          │",
+            "└──────────────┘└──────────────────────────────────────────┘",
+        ]));
+
+        // Diplomacy and Military are followed by every message again
+        for _ in 0..3 {
+            TerminalRenderer::<TestBackend>::cycle_filter(&mut message_log_menu);
+        }
+
+        assert_eq!(draw(&mut terminal, &mut message_log_menu, UIType::MessageLog, &game_state, None), all);
+    }
+}
@@ -1,21 +1,26 @@
 use tui::{
-    backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout, Alignment},
-    style::{Color, Style},
-    Terminal,
+    backend::Backend,
+    layout::Alignment,
+    style::Style,
+    Frame,
 };
 
-use std::io::Stdout;
-use std::sync::{Arc,RwLock};
-use tui::widgets::{Block, Borders, List, Paragraph};
+use tui::widgets::{Block, Borders, Paragraph};
 use crate::data::TerminalDisplay;
 use crate::game::GameState;
-use crate::menu::rendering::TerminalRenderer;
+use crate::menu::view_registry::ViewRegistry;
+use crate::menu::rendering::{page_height, split_view, TerminalRenderer, ViewLayout};
+use crate::menu::rendering::scrollbar::Scrollbar;
+use crate::menu::themes::Theme;
 
 /// A view, which displays a player's crafts
 pub struct CraftMenu{
     /// The scrolling offset in case of bigger menues
     scroll_offset: u16,
+    /// The number of lines, which have been displayed in the last rendering
+    content_height: u16,
+    /// The areas, which the view has covered in its last rendering
+    layout: ViewLayout,
 }
 
 impl CraftMenu{
@@ -23,42 +28,52 @@ impl CraftMenu{
     pub fn new() -> CraftMenu{
         CraftMenu{
             scroll_offset: 0,
+            content_height: 0,
+            layout: ViewLayout::default(),
         }
     }
+
+    /// Returns the greatest scrolling offset, at which the last line is displayed at the bottom
+    fn max_scroll_offset(&self) -> u16{
+        self.content_height.saturating_sub(page_height(self.layout.content))
+    }
 }
 
-impl TerminalRenderer for CraftMenu{
+impl<B: Backend> TerminalRenderer<B> for CraftMenu{
 
     /// Renders the CraftMenu in the terminal
-    fn render(&mut self, terminal: &mut Terminal<CrosstermBackend<Stdout>>, menu_widget : &List, game_state: Arc<RwLock<GameState>>) {
-
-        let main_style : Style = Style::default().bg(Color::Rgb(50, 25, 0));
-
-        terminal.draw(|f| {
-            let chunks = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints([Constraint::Percentage(15), Constraint::Percentage(85)].as_ref())
-                .split(f.size());
-
-            let crafts: String = game_state.read().unwrap().kingdom.crafts.iter()
-                .map(|(t,v)| {
-                    let formatted_crafts : String = v.iter()
-                        .map(|c| c.display())
-                        .collect();
-                    format!( "{}:\n {formatted_crafts}", t)
-                })
-                .collect();
-
-            let craft_screen = Paragraph::new(crafts)
-                .style(main_style)
-                .alignment(Alignment::Center)
-                .scroll((self.get_scroll_offset(),0))
-                .block(Block::default().borders(Borders::ALL).title("Main"));
-
-            f.render_widget(craft_screen, chunks[1]);
-
-            f.render_widget(menu_widget.clone(), chunks[0]);
-        }).expect("Can render widget");
+    fn render(&mut self, frame: &mut Frame<B>, menu : &ViewRegistry, game_state: &GameState, theme: &Theme) {
+
+        let main_style : Style = theme.main_style();
+
+        let chunks = split_view(frame.size(), menu);
+
+        let crafts: String = game_state.kingdom.crafts.iter()
+            .map(|(t,v)| {
+                let formatted_crafts : String = v.iter()
+                    .map(|c| c.display())
+                    .collect();
+                format!( "{}:\n {formatted_crafts}", t)
+            })
+            .collect();
+
+        self.content_height = crafts.lines().count() as u16;
+        self.layout = ViewLayout { menu: chunks[0], content: chunks[1] };
+
+        // The content can't be scrolled beyond its end, e.g. after the terminal has been enlarged
+        self.scroll_offset = self.scroll_offset.min(self.max_scroll_offset());
+
+        let craft_screen = Paragraph::new(crafts)
+            .style(main_style)
+            .alignment(Alignment::Center)
+            .scroll((self.scroll_offset,0))
+            .block(Block::default().borders(Borders::ALL).title(menu.breadcrumbs()));
+
+        frame.render_widget(craft_screen, chunks[1]);
+
+        Scrollbar::new(self.scroll_offset, self.max_scroll_offset()).render(frame, chunks[1], theme);
+
+        menu.render(frame, chunks[0], theme);
     }
 
     fn set_scroll_offset(&mut self, offset: u16) {
@@ -68,4 +83,12 @@ impl TerminalRenderer for CraftMenu{
     fn get_scroll_offset(&self) -> u16 {
         self.scroll_offset
     }
+
+    fn get_max_scroll_offset(&self) -> u16 {
+        self.max_scroll_offset()
+    }
+
+    fn get_layout(&self) -> ViewLayout {
+        self.layout
+    }
 }
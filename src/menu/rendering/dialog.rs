@@ -0,0 +1,142 @@
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Alignment},
+    style::Style,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::menu::rendering::centered_rect;
+use crate::menu::themes::Theme;
+
+/// Confirmation contains the answers of a confirmation dialog in the order, in which they are offered
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Confirmation{
+    Yes,
+    No,
+    Cancel,
+}
+
+impl Confirmation{
+
+    /// Every answer of a confirmation dialog in the order, in which they are offered
+    const ALL: [Confirmation; 3] = [Confirmation::Yes, Confirmation::No, Confirmation::Cancel];
+
+    /// Returns the answer for the index of a confirmation dialog's selected option. Any index out of
+    /// range is treated as Cancel.
+    /// # Arguments
+    /// * `index` is the index of the selected option
+    pub fn from_index(index: usize) -> Confirmation{
+        Confirmation::ALL.get(index).copied().unwrap_or(Confirmation::Cancel)
+    }
+
+    /// Returns the label of the answer, which is displayed in the dialog
+    pub fn label(&self) -> &'static str{
+        match *self{
+            Confirmation::Yes => "Yes",
+            Confirmation::No => "No",
+            Confirmation::Cancel => "Cancel",
+        }
+    }
+}
+
+/// A modal dialog, which is drawn on top of the current view. It displays a message and offers
+/// the player a set of options, of which exactly one is selected at any time.
+pub struct Dialog{
+    /// The title of the dialog
+    title: String,
+    /// The message, which is displayed above the options
+    message: String,
+    /// The options, which the player can choose from
+    options: Vec<String>,
+    /// The index of the currently selected option
+    selected: usize,
+}
+
+impl Dialog{
+
+    /// Creates a new dialog, in which the first option is selected
+    /// # Arguments
+    /// * `title` is the title of the dialog
+    /// * `message` is the message, which is displayed above the options
+    /// * `options` are the options, which the player can choose from
+    pub fn new(title: String, message: String, options: Vec<String>) -> Dialog{
+        Dialog{
+            title,
+            message,
+            options,
+            selected: 0,
+        }
+    }
+
+    /// Creates a new confirmation dialog, which offers the answers Yes, No and Cancel. The selected
+    /// option can be turned into an answer with `Confirmation::from_index`.
+    /// # Arguments
+    /// * `title` is the title of the dialog
+    /// * `message` is the question, which the player is asked
+    pub fn confirmation(title: String, message: String) -> Dialog{
+        Dialog::new(title, message, Confirmation::ALL.iter().map(|c| String::from(c.label())).collect())
+    }
+
+    /// Selects the option above the currently selected one, if there is any
+    pub fn select_previous(&mut self){
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Selects the option below the currently selected one, if there is any
+    pub fn select_next(&mut self){
+        if self.selected + 1 < self.options.len(){
+            self.selected += 1;
+        }
+    }
+
+    /// Returns the index of the currently selected option
+    pub fn get_selected(&self) -> usize{
+        self.selected
+    }
+
+    /// Draws the dialog in the center of the frame, covering whatever has been drawn there before
+    /// # Arguments
+    /// * `frame` is the terminal's frame in which the dialog shall be drawn
+    /// * `theme` is the theme, whose colors are used
+    pub fn render<B: Backend>(&self, frame: &mut Frame<B>, theme: &Theme){
+
+        let main_style : Style = theme.main_style();
+        let highlight_style : Style = theme.highlight_style();
+
+        let area = centered_rect(60, 50, frame.size());
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(self.title.as_str())
+            .style(main_style);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([Constraint::Min(1), Constraint::Length(self.options.len() as u16)].as_ref())
+            .split(area);
+
+        let message = Paragraph::new(self.message.as_str())
+            .style(main_style)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+
+        let options: Vec<ListItem> = self.options.iter()
+            .map(|o| ListItem::new(o.as_str()))
+            .collect();
+
+        let options = List::new(options)
+            .style(main_style)
+            .highlight_style(highlight_style)
+            .highlight_symbol("> ");
+
+        let mut state = ListState::default();
+        state.select(Some(self.selected));
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+        frame.render_widget(message, chunks[0]);
+        frame.render_stateful_widget(options, chunks[1], &mut state);
+    }
+}
@@ -0,0 +1,156 @@
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use std::collections::VecDeque;
+use std::ops::Add;
+use std::time::{Duration, Instant};
+use crate::menu::ui_foundations::UIError;
+use crate::menu::themes::Theme;
+
+/// The number of errors, which are kept at the same time
+const MAX_ERRORS: usize = 5;
+
+/// The duration, for which an error is displayed
+const ERROR_DURATION: Duration = Duration::from_secs(8);
+
+/// A banner, which displays recoverable errors at the top of the terminal. It is drawn on top of
+/// every view and overlay.
+pub struct ErrorBanner{
+    /// The displayed errors together with their expiration times, the oldest one first
+    errors: VecDeque<(UIError, Instant)>,
+}
+
+impl ErrorBanner{
+
+    /// Creates a banner without any errors
+    pub fn new() -> ErrorBanner{
+        ErrorBanner{
+            errors: VecDeque::new(),
+        }
+    }
+
+    /// Adds an error to the banner. If there are too many errors, the oldest one is dropped.
+    /// # Arguments
+    /// * `error` is the new error
+    pub fn push(&mut self, error: UIError){
+
+        if self.errors.len() == MAX_ERRORS {
+            self.errors.pop_front();
+        }
+
+        self.errors.push_back((error, Instant::now().add(ERROR_DURATION)));
+    }
+
+    /// Removes every error, whose expiration time lies in the past. Returns true, if any error has
+    /// been removed.
+    pub fn remove_expired(&mut self) -> bool{
+        let now = Instant::now();
+        let len = self.errors.len();
+        self.errors.retain(|(_, expiration_time)| expiration_time.gt(&now));
+        self.errors.len() != len
+    }
+
+    /// Returns the time, at which the oldest error expires, or None, if there is none
+    pub fn next_expiration_time(&self) -> Option<Instant>{
+        self.errors.front().map(|(_, expiration_time)| *expiration_time)
+    }
+
+    /// Draws the newest error at the top of the frame. If there are older errors, their number is
+    /// displayed in the banner's title.
+    /// # Arguments
+    /// * `frame` is the terminal's frame in which the banner shall be drawn
+    /// * `theme` is the theme, whose colors are used
+    pub fn render<B: Backend>(&self, frame: &mut Frame<B>, theme: &Theme){
+
+        let error_style : Style = theme.error_style();
+
+        if let Some((error, _)) = self.errors.back() {
+
+            let size = frame.size();
+            let area = Rect::new(size.x, size.y, size.width, size.height.min(3));
+
+            let title = match self.errors.len() {
+                1 => String::from("Error"),
+                n => format!("Error (+{} more)", n - 1),
+            };
+
+            let banner = Paragraph::new(error.to_string())
+                .style(error_style)
+                .wrap(Wrap { trim: true })
+                .block(Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .style(error_style.add_modifier(Modifier::BOLD)));
+
+            frame.render_widget(Clear, area);
+            frame.render_widget(banner, area);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use tui::{backend::TestBackend, Terminal};
+
+    use super::*;
+
+    /// Draws the banner into a terminal of 40x5 and returns the drawn rows
+    /// # Arguments
+    /// * `error_banner` is the banner, which is drawn
+    fn draw(error_banner: &ErrorBanner) -> Vec<String> {
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 5)).unwrap();
+        terminal.draw(|f| error_banner.render(f, &Theme::default())).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        (0..5).map(|y| (0..40).map(|x| buffer.get(x, y).symbol.as_str()).collect()).collect()
+    }
+
+    #[test]
+    fn the_newest_error_is_displayed_with_the_number_of_older_ones() {
+
+        let mut error_banner = ErrorBanner::new();
+        assert_eq!(draw(&error_banner), vec![" ".repeat(40); 5]);
+
+        error_banner.push(UIError::Save(String::from("Disk full")));
+        assert_eq!(draw(&error_banner)[..3], [
+            "┌Error─────────────────────────────────┐",
+            "│Unable to save the game: Disk full    │",
+            "└──────────────────────────────────────┘",
+        ]);
+
+        error_banner.push(UIError::Command(String::from("No village 9")));
+        assert_eq!(draw(&error_banner)[..3], [
+            "┌Error (+1 more)───────────────────────┐",
+            "│Unable to apply the command: No       │",
+            "└──────────────────────────────────────┘",
+        ]);
+    }
+
+    #[test]
+    fn only_the_newest_errors_are_kept_until_they_expire() {
+
+        let mut error_banner = ErrorBanner::new();
+
+        for i in 0..MAX_ERRORS + 2 {
+            error_banner.push(UIError::Input(format!("Error {i}")));
+        }
+
+        assert_eq!(error_banner.errors.len(), MAX_ERRORS);
+        assert_eq!(error_banner.errors.front().unwrap().0.to_string(), "Unable to read input: Error 2");
+        assert!(!error_banner.remove_expired());
+
+        // The oldest error expires first
+        error_banner.errors[0].1 = Instant::now() - Duration::from_secs(1);
+        assert_eq!(error_banner.next_expiration_time(), Some(error_banner.errors[0].1));
+        assert!(error_banner.remove_expired());
+        assert_eq!(error_banner.errors.len(), MAX_ERRORS - 1);
+        assert_eq!(error_banner.errors.front().unwrap().0.to_string(), "Unable to read input: Error 3");
+    }
+}
@@ -1,61 +1,56 @@
 use tui::{
-    backend::CrosstermBackend,
+    backend::Backend,
     layout::{Constraint, Layout, Alignment},
-    style::{Color, Style},
-    widgets::{Block, Borders, List, Paragraph},
-    Terminal,
+    style::Style,
+    widgets::{Block, Borders, Paragraph},
+    Frame,
 };
 
-use std::io::Stdout;
-use std::sync::{Arc,RwLock};
-use std::thread;
-use std::time::Duration;
 use crate::game::GameState;
+use crate::menu::view_registry::ViewRegistry;
 use crate::menu::ui_foundations::UIType;
 use crate::menu::assets;
 use crate::menu::rendering::TerminalRenderer;
+use crate::menu::themes::Theme;
 
-/// A view, which displays a fullscreen message
+/// A view, which displays a fullscreen message. How long the message is displayed is determined
+/// by the expiration time of the current ui.
 pub struct FullScreenMessage {
     /// A view type, e.g. in order to differentiate between the startup- and termination message
     pub ui_type: UIType,
 }
 
-impl TerminalRenderer for FullScreenMessage {
+impl<B: Backend> TerminalRenderer<B> for FullScreenMessage {
 
     /// Draws a message over the full screen of the terminal.
-    fn render(&mut self, terminal: &mut Terminal<CrosstermBackend<Stdout>>, _menu_widget : &List, _game_state: Arc<RwLock<GameState>>) {
+    fn render(&mut self, frame: &mut Frame<B>, _menu : &ViewRegistry, _game_state: &GameState, theme: &Theme) {
 
         let mut msg = None;
 
         match self.ui_type{
             UIType::Start => {
-                msg = Some(assets::read_asset("welcome.txt"));
+                msg = Some(assets::read_asset("welcome.txt").unwrap_or_else(|e| e.to_string()));
             }
             UIType::Quit => {
-                msg = Some(assets::read_asset("exit.txt"));
+                msg = Some(assets::read_asset("exit.txt").unwrap_or_else(|e| e.to_string()));
             }
             _ => {}
         }
 
         if let Some(msg) = msg {
 
-            let main_style : Style = Style::default().bg(Color::Rgb(50, 25, 0));
+            let main_style : Style = theme.main_style();
 
             let widget = Paragraph::new(msg)
                 .style(main_style)
                 .alignment(Alignment::Center)
                 .block(Block::default().borders(Borders::ALL).title("Railan"));
 
-            terminal.draw(move |f| {
-                let layout = Layout::default()
-                    .constraints([Constraint::Percentage(100)].as_ref())
-                    .split(f.size());
+            let layout = Layout::default()
+                .constraints([Constraint::Percentage(100)].as_ref())
+                .split(frame.size());
 
-                f.render_widget(widget, layout[0]);
-            }).expect("Can render widget");
-
-            thread::sleep(Duration::from_secs(3));
+            frame.render_widget(widget, layout[0]);
         }
     }
 
@@ -66,4 +61,4 @@ impl TerminalRenderer for FullScreenMessage {
     fn get_scroll_offset(&self) -> u16 {
         0
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,66 @@
+use tui::{
+    backend::Backend,
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::menu::rendering::centered_rect;
+use crate::menu::ui_foundations::UIType;
+use crate::menu::themes::Theme;
+
+/// An overlay, which lists every key binding, which has an effect in the current view, together
+/// with its description
+pub struct HelpOverlay{
+    /// The type of the view, for which the help is displayed
+    ui_type: UIType,
+    /// The label of every key chord together with the description of its action
+    entries: Vec<(String, String)>,
+}
+
+impl HelpOverlay{
+
+    /// Creates a new help overlay
+    /// # Arguments
+    /// * `ui_type` is the type of the view, for which the help is displayed
+    /// * `entries` are the labels of the key chords together with the descriptions of their actions
+    pub fn new(ui_type: UIType, entries: Vec<(String, String)>) -> HelpOverlay{
+        HelpOverlay{
+            ui_type,
+            entries,
+        }
+    }
+
+    /// Draws the help in the center of the frame, covering whatever has been drawn there before
+    /// # Arguments
+    /// * `frame` is the terminal's frame in which the help shall be drawn
+    /// * `theme` is the theme, whose colors are used
+    pub fn render<B: Backend>(&self, frame: &mut Frame<B>, theme: &Theme){
+
+        let main_style : Style = theme.main_style();
+        let key_style : Style = Style::default().add_modifier(Modifier::BOLD);
+
+        let area = centered_rect(70, 70, frame.size());
+
+        let width = self.entries.iter()
+            .map(|(label, _)| label.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let lines: Vec<Spans> = self.entries.iter()
+            .map(|(label, description)| Spans::from(vec![
+                Span::styled(format!(" {label:width$}   "), key_style),
+                Span::raw(description.as_str()),
+            ]))
+            .collect();
+
+        let help = Paragraph::new(lines)
+            .style(main_style)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::ALL).title(format!("Help - {}", self.ui_type.title())));
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(help, area);
+    }
+}
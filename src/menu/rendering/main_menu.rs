@@ -1,43 +1,51 @@
 use tui::{
-    backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout, Alignment},
-    style::{Color, Style},
-    widgets::{Block, Borders, List, Paragraph},
-    Terminal,
+    backend::Backend,
+    layout::Alignment,
+    style::Style,
+    widgets::{Block, Borders, Paragraph},
+    Frame,
 };
 
-use std::io::Stdout;
-use std::sync::{Arc,RwLock};
 use crate::game::GameState;
+use crate::menu::view_registry::ViewRegistry;
 use crate::menu::assets;
-use crate::menu::rendering::TerminalRenderer;
+use crate::menu::rendering::{split_view, TerminalRenderer, ViewLayout};
+use crate::menu::themes::Theme;
 
-/// A view, which displays a fullscreen message
-pub struct MainMenu;
+/// The main view, which is displayed next to the menu
+pub struct MainMenu{
+    /// The areas, which the view has covered in its last rendering
+    layout: ViewLayout,
+}
+
+impl MainMenu{
+    /// Initialize MainMenu without any layout
+    pub fn new() -> MainMenu{
+        MainMenu{
+            layout: ViewLayout::default(),
+        }
+    }
+}
 
-impl TerminalRenderer for MainMenu{
+impl<B: Backend> TerminalRenderer<B> for MainMenu{
 
     /// Draws the Main Menu in the terminal
-    fn render(&mut self, terminal: &mut Terminal<CrosstermBackend<Stdout>>, menu_widget : &List, _game_state: Arc<RwLock<GameState>>) {
+    fn render(&mut self, frame: &mut Frame<B>, menu : &ViewRegistry, _game_state: &GameState, theme: &Theme) {
 
-        let main_style : Style = Style::default().bg(Color::Rgb(50, 25, 0));
+        let main_style : Style = theme.main_style();
 
-        let main = Paragraph::new(assets::read_asset("main.txt"))
-            .style(Style::default().fg(Color::White))
+        let main = Paragraph::new(assets::read_asset("main.txt").unwrap_or_else(|e| e.to_string()))
             .alignment(Alignment::Center)
             .style(main_style)
-            .block(Block::default().borders(Borders::ALL).title("Main"));
+            .block(Block::default().borders(Borders::ALL).title(menu.breadcrumbs()));
 
-        terminal.draw(|f| {
-            let chunks = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints([Constraint::Percentage(15), Constraint::Percentage(85)].as_ref())
-                .split(f.size());
+        let chunks = split_view(frame.size(), menu);
 
-            f.render_widget(menu_widget.clone(), chunks[0]);
+        menu.render(frame, chunks[0], theme);
 
-            f.render_widget(main.clone(), chunks[1]);
-        }).expect("Can render widget");
+        frame.render_widget(main, chunks[1]);
+
+        self.layout = ViewLayout { menu: chunks[0], content: chunks[1] };
     }
 
     fn set_scroll_offset(&mut self, _offset: u16) {
@@ -47,4 +55,8 @@ impl TerminalRenderer for MainMenu{
     fn get_scroll_offset(&self) -> u16 {
         0
     }
+
+    fn get_layout(&self) -> ViewLayout {
+        self.layout
+    }
 }
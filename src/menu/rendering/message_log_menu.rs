@@ -0,0 +1,111 @@
+use tui::{
+    backend::Backend,
+    layout::Alignment,
+    style::Style,
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::game::GameState;
+use crate::menu::view_registry::ViewRegistry;
+use crate::game::messages::MessageCategory;
+use crate::menu::rendering::{page_height, split_view, TerminalRenderer, ViewLayout};
+use crate::menu::rendering::scrollbar::Scrollbar;
+use crate::menu::themes::Theme;
+
+/// A view, which displays the log of every message, which has been posted during the game. The
+/// log can be filtered by category.
+pub struct MessageLogMenu{
+    /// The scrolling offset in case of bigger menues
+    scroll_offset: u16,
+    /// The number of lines, which have been displayed in the last rendering
+    content_height: u16,
+    /// The category, by which the messages are filtered. If it is None, every message is displayed.
+    filter: Option<MessageCategory>,
+    /// The areas, which the view has covered in its last rendering
+    layout: ViewLayout,
+}
+
+impl MessageLogMenu{
+    /// Initialize MessageLogMenu with a scrolling offset of 0 and without any filter
+    pub fn new() -> MessageLogMenu{
+        MessageLogMenu{
+            scroll_offset: 0,
+            content_height: 0,
+            filter: None,
+            layout: ViewLayout::default(),
+        }
+    }
+
+    /// Returns the greatest scrolling offset, at which the last line is displayed at the bottom
+    fn max_scroll_offset(&self) -> u16{
+        self.content_height.saturating_sub(page_height(self.layout.content))
+    }
+}
+
+impl<B: Backend> TerminalRenderer<B> for MessageLogMenu{
+
+    /// Draws the message log to the terminal, the newest message first
+    fn render(&mut self, frame: &mut Frame<B>, menu : &ViewRegistry, game_state: &GameState, theme: &Theme) {
+
+        let main_style : Style = theme.main_style();
+
+        let chunks = split_view(frame.size(), menu);
+
+        let messages: String = game_state.message_log.iter()
+            .rev()
+            .filter(|m| self.filter.is_none() || self.filter == Some(m.category))
+            .map(|m| format!("{m}\n"))
+            .collect();
+
+        let title = match self.filter {
+            None => format!("{} (All) - [F]ilter", menu.breadcrumbs()),
+            Some(category) => format!("{} ({category}) - [F]ilter", menu.breadcrumbs()),
+        };
+
+        self.content_height = messages.lines().count() as u16;
+        self.layout = ViewLayout { menu: chunks[0], content: chunks[1] };
+
+        // The content can't be scrolled beyond its end, e.g. after messages have been filtered
+        self.scroll_offset = self.scroll_offset.min(self.max_scroll_offset());
+
+        let log_screen = Paragraph::new(messages)
+            .style(main_style)
+            .alignment(Alignment::Left)
+            .scroll((self.scroll_offset,0))
+            .block(Block::default().borders(Borders::ALL).title(title));
+
+        frame.render_widget(log_screen, chunks[1]);
+
+        Scrollbar::new(self.scroll_offset, self.max_scroll_offset()).render(frame, chunks[1], theme);
+
+        menu.render(frame, chunks[0], theme);
+    }
+
+    fn set_scroll_offset(&mut self, offset: u16) {
+        self.scroll_offset = offset;
+    }
+
+    fn get_scroll_offset(&self) -> u16 {
+        self.scroll_offset
+    }
+
+    fn get_max_scroll_offset(&self) -> u16 {
+        self.max_scroll_offset()
+    }
+
+    fn get_layout(&self) -> ViewLayout {
+        self.layout
+    }
+
+    /// Cycles through the filters: All, Economy, Diplomacy, Military and back to All
+    fn cycle_filter(&mut self) {
+        self.filter = match self.filter {
+            None => Some(MessageCategory::Economy),
+            Some(MessageCategory::Economy) => Some(MessageCategory::Diplomacy),
+            Some(MessageCategory::Diplomacy) => Some(MessageCategory::Military),
+            Some(MessageCategory::Military) => None,
+        };
+        self.scroll_offset = 0;
+    }
+}
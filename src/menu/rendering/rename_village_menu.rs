@@ -0,0 +1,86 @@
+use tui::{
+    backend::Backend,
+    layout::Alignment,
+    style::Style,
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::game::GameState;
+use crate::menu::view_registry::ViewRegistry;
+use crate::menu::rendering::{split_view, TerminalRenderer, ViewLayout};
+use crate::menu::rendering::text_input::TextInput;
+use crate::menu::themes::Theme;
+
+/// A view, in which the player types a new name for a village. It is opened from the detail view
+/// of the village.
+pub struct RenameVillageMenu{
+    /// The index of the village, which is renamed
+    village: usize,
+    /// The new name, which the player types
+    input: TextInput,
+    /// The areas, which the view has covered in its last rendering
+    layout: ViewLayout,
+}
+
+impl RenameVillageMenu{
+    /// Initialize RenameVillageMenu for the first village with an empty name
+    pub fn new() -> RenameVillageMenu{
+        RenameVillageMenu{
+            village: 0,
+            input: TextInput::new(),
+            layout: ViewLayout::default(),
+        }
+    }
+}
+
+impl<B: Backend> TerminalRenderer<B> for RenameVillageMenu{
+
+    /// Draws the text input for the village's new name to the terminal
+    fn render(&mut self, frame: &mut Frame<B>, menu : &ViewRegistry, game_state: &GameState, theme: &Theme) {
+
+        let main_style : Style = theme.main_style();
+
+        let chunks = split_view(frame.size(), menu);
+
+        let old_name = game_state.kingdom.villages.get(self.village)
+            .map_or("", |v| v.name.as_str());
+
+        let rename_screen = Paragraph::new(format!("New name for {old_name}:\n\n{}_\n\n[Enter] Confirm  [Esc] Cancel", self.input.get_text()))
+            .style(main_style)
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::ALL).title(menu.breadcrumbs()));
+
+        frame.render_widget(rename_screen, chunks[1]);
+
+        menu.render(frame, chunks[0], theme);
+
+        self.layout = ViewLayout { menu: chunks[0], content: chunks[1] };
+    }
+
+    fn set_scroll_offset(&mut self, _offset: u16) {
+        // nothing to do
+    }
+
+    fn get_scroll_offset(&self) -> u16 {
+        0
+    }
+
+    fn get_layout(&self) -> ViewLayout {
+        self.layout
+    }
+
+    fn get_selection(&self) -> usize {
+        self.village
+    }
+
+    /// Starts renaming the given village with an empty name
+    fn set_parent_selection(&mut self, selection: usize) {
+        self.village = selection;
+        self.input.clear();
+    }
+
+    fn text_input_mut(&mut self) -> Option<&mut TextInput> {
+        Some(&mut self.input)
+    }
+}
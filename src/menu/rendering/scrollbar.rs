@@ -0,0 +1,57 @@
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::menu::themes::Theme;
+
+/// An indicator on the right border of a scrollable view, which shows how far the view has been
+/// scrolled. It consists of a single thumb, which moves from the top to the bottom of the border.
+pub struct Scrollbar{
+    /// The current scrolling position
+    position: u16,
+    /// The scrolling position, at which the end of the content is reached
+    max_position: u16,
+}
+
+impl Scrollbar{
+
+    /// Creates a scrollbar for the given scrolling position
+    /// # Arguments
+    /// * `position` is the current scrolling position
+    /// * `max_position` is the scrolling position, at which the end of the content is reached
+    pub fn new(position: u16, max_position: u16) -> Scrollbar{
+        Scrollbar{
+            position,
+            max_position,
+        }
+    }
+
+    /// Draws the scrollbar over the right border of a bordered view. If there is nothing to
+    /// scroll, the border is left as it is.
+    /// # Arguments
+    /// * `frame` is the terminal's frame in which the scrollbar shall be drawn
+    /// * `area` is the area of the view including its borders
+    /// * `theme` is the theme, whose colors are used
+    pub fn render<B: Backend>(&self, frame: &mut Frame<B>, area: Rect, theme: &Theme){
+
+        let track_height = area.height.saturating_sub(2);
+
+        if self.max_position == 0 || track_height == 0 || area.width == 0 {
+            return;
+        }
+
+        let thumb = (self.position.min(self.max_position) as u32 * (track_height - 1) as u32
+            / self.max_position as u32) as u16;
+
+        let track: String = (0..track_height)
+            .map(|row| if row == thumb { "█\n" } else { "│\n" })
+            .collect();
+
+        let track_area = Rect::new(area.right() - 1, area.y + 1, 1, track_height);
+
+        frame.render_widget(Paragraph::new(track).style(theme.main_style()), track_area);
+    }
+}
@@ -0,0 +1,101 @@
+use tui::{
+    backend::Backend,
+    style::Style,
+    widgets::{Block, Borders, List, ListItem, ListState},
+    Frame,
+};
+
+use crate::game::GameState;
+use crate::menu::view_registry::ViewRegistry;
+use crate::menu::rendering::{list_index_at, page_height, split_view, TerminalRenderer, ViewLayout};
+use crate::menu::rendering::scrollbar::Scrollbar;
+use crate::menu::themes::Theme;
+
+/// A view, which displays the settings. Currently, the player can choose a theme. The scrolling
+/// offset selects one of the themes, which may be applied.
+pub struct SettingsMenu{
+    /// The scrolling offset, which equals the index of the selected theme
+    scroll_offset: u16,
+    /// The names of every theme, which can be chosen
+    theme_names: Vec<String>,
+    /// The areas, which the view has covered in its last rendering
+    layout: ViewLayout,
+}
+
+impl SettingsMenu{
+    /// Initialize SettingsMenu with a scrolling offset of 0
+    /// # Arguments
+    /// * `theme_names` are the names of the themes, which can be chosen
+    pub fn new(theme_names: Vec<String>) -> SettingsMenu{
+        SettingsMenu{
+            scroll_offset: 0,
+            theme_names,
+            layout: ViewLayout::default(),
+        }
+    }
+}
+
+impl<B: Backend> TerminalRenderer<B> for SettingsMenu{
+
+    /// Draws the themes to the terminal, wherein the active theme is marked
+    fn render(&mut self, frame: &mut Frame<B>, menu : &ViewRegistry, _game_state: &GameState, theme: &Theme) {
+
+        let main_style : Style = theme.main_style();
+        let highlight_style : Style = theme.highlight_style();
+
+        let chunks = split_view(frame.size(), menu);
+
+        // The selection can't move beyond the last theme
+        self.scroll_offset = self.scroll_offset.min(self.theme_names.len().saturating_sub(1) as u16);
+
+        let items: Vec<ListItem> = self.theme_names.iter()
+            .map(|name| if *name == theme.name {
+                ListItem::new(format!("{name} (active)"))
+            } else {
+                ListItem::new(name.as_str())
+            })
+            .collect();
+
+        let settings_screen = List::new(items)
+            .style(main_style)
+            .highlight_style(highlight_style)
+            .highlight_symbol("> ")
+            .block(Block::default().borders(Borders::ALL).title(format!("{} - Theme", menu.breadcrumbs())));
+
+        let mut state = ListState::default();
+        state.select(Some(self.scroll_offset as usize));
+
+        frame.render_stateful_widget(settings_screen, chunks[1], &mut state);
+
+        self.layout = ViewLayout { menu: chunks[0], content: chunks[1] };
+
+        // The scrollbar is only needed, if not every theme fits into the view
+        if self.theme_names.len() > page_height(self.layout.content) as usize {
+            Scrollbar::new(self.scroll_offset, self.theme_names.len().saturating_sub(1) as u16).render(frame, chunks[1], theme);
+        }
+
+        menu.render(frame, chunks[0], theme);
+    }
+
+    fn set_scroll_offset(&mut self, offset: u16) {
+        self.scroll_offset = offset;
+    }
+
+    fn get_scroll_offset(&self) -> u16 {
+        self.scroll_offset
+    }
+
+    fn get_max_scroll_offset(&self) -> u16 {
+        self.theme_names.len().saturating_sub(1) as u16
+    }
+
+    fn get_layout(&self) -> ViewLayout {
+        self.layout
+    }
+
+    fn select_at(&mut self, row: u16) {
+        if let Some(index) = list_index_at(self.layout.content, row, self.scroll_offset as usize, self.theme_names.len()) {
+            self.scroll_offset = index as u16;
+        }
+    }
+}
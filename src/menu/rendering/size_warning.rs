@@ -0,0 +1,122 @@
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+
+use crate::menu::themes::Theme;
+
+/// The minimum width of the terminal, at which every view can be displayed without being garbled
+const MIN_TERMINAL_WIDTH: u16 = 80;
+
+/// The minimum height of the terminal, at which every view can be displayed without being garbled
+const MIN_TERMINAL_HEIGHT: u16 = 24;
+
+/// A screen, which is displayed instead of any view, as long as the terminal is too small
+pub struct SizeWarning{
+    /// The minimum width of the terminal
+    min_width: u16,
+    /// The minimum height of the terminal
+    min_height: u16,
+}
+
+impl SizeWarning{
+
+    /// Creates a warning for the minimum size, which every view needs
+    pub fn new() -> SizeWarning{
+        SizeWarning{
+            min_width: MIN_TERMINAL_WIDTH,
+            min_height: MIN_TERMINAL_HEIGHT,
+        }
+    }
+
+    /// Returns true, if the area is smaller than the minimum size in any dimension
+    /// # Arguments
+    /// * `area` is the area of the whole terminal
+    pub fn is_too_small(&self, area: Rect) -> bool{
+        area.width < self.min_width || area.height < self.min_height
+    }
+
+    /// Draws the warning over the whole frame. It is wrapped, so that it stays readable in tiny
+    /// terminals.
+    /// # Arguments
+    /// * `frame` is the terminal's frame in which the warning shall be drawn
+    /// * `theme` is the theme, whose colors are used
+    pub fn render<B: Backend>(&self, frame: &mut Frame<B>, theme: &Theme){
+
+        let main_style : Style = theme.main_style();
+
+        let size = frame.size();
+
+        let text = format!(
+            "Terminal too small, need at least {}x{}.\nCurrently it is {}x{}.",
+            self.min_width, self.min_height, size.width, size.height
+        );
+
+        // Center the text vertically as far as the terminal's height allows
+        let top = size.height.saturating_sub(2) / 2;
+        let area = Rect::new(size.x, size.y + top, size.width, size.height - top);
+
+        let warning = Paragraph::new(text)
+            .style(main_style.add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+
+        frame.render_widget(Paragraph::new("").style(main_style), size);
+        frame.render_widget(warning, area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use tui::{backend::TestBackend, Terminal};
+
+    use super::*;
+
+    /// Draws the warning into a terminal of the given size and returns the drawn rows
+    /// # Arguments
+    /// * `width` is the width of the terminal
+    /// * `height` is the height of the terminal
+    fn draw(width: u16, height: u16) -> Vec<String> {
+
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|f| SizeWarning::new().render(f, &Theme::default())).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        (0..height).map(|y| (0..width).map(|x| buffer.get(x, y).symbol.as_str()).collect()).collect()
+    }
+
+    #[test]
+    fn terminals_below_the_minimum_size_are_too_small() {
+
+        let size_warning = SizeWarning::new();
+
+        assert!(!size_warning.is_too_small(Rect::new(0, 0, MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT)));
+        assert!(!size_warning.is_too_small(Rect::new(0, 0, 200, 60)));
+        assert!(size_warning.is_too_small(Rect::new(0, 0, MIN_TERMINAL_WIDTH - 1, MIN_TERMINAL_HEIGHT)));
+        assert!(size_warning.is_too_small(Rect::new(0, 0, MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT - 1)));
+        assert!(size_warning.is_too_small(Rect::new(0, 0, 0, 0)));
+    }
+
+    #[test]
+    fn the_warning_states_the_minimum_and_the_current_size() {
+
+        assert_eq!(draw(44, 4), vec![
+            "                                            ",
+            "  Terminal too small, need at least 80x24.  ",
+            "            Currently it is 44x4.           ",
+            "                                            ",
+        ]);
+    }
+
+    #[test]
+    fn the_warning_is_drawn_into_tiny_terminals() {
+
+        for (width, height) in [(1, 1), (10, 1), (1, 10), (0, 0)] {
+            draw(width, height);
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
+    symbols::Marker,
+    text::Span,
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, List, ListItem, ListState, Paragraph, Sparkline},
+    Frame,
+};
+
+use crate::data::craft::CraftType;
+use crate::game::GameState;
+use crate::game::statistics::{Sample, StatisticsHistory};
+use crate::menu::view_registry::ViewRegistry;
+use crate::menu::rendering::{list_index_at, page_height, split_view, TerminalRenderer, ViewLayout};
+use crate::menu::rendering::scrollbar::Scrollbar;
+use crate::menu::themes::Theme;
+
+/// The time windows, which the statistics can be plotted for, in days. None stands for the whole
+/// history.
+const TIME_WINDOWS: [Option<u64>; 4] = [Some(30), Some(100), Some(365), None];
+
+/// The height of the sparklines, which give an overview of the kingdom, including their borders
+const OVERVIEW_HEIGHT: u16 = 5;
+
+/// The width of the list of series including its borders
+const SERIES_LIST_WIDTH: u16 = 32;
+
+/// A statistic of the kingdom, whose history can be plotted
+#[derive(Debug, Copy, Clone)]
+enum Statistic {
+    /// The gold in the treasury
+    Treasury,
+    /// The number of citizens of every village together
+    TotalCitizens,
+    /// The number of citizens of the village with the given index
+    Citizens(usize),
+    /// The levels of every craft together
+    TotalCraftLevels,
+    /// The summed up levels of the given type of craft
+    CraftLevels(CraftType),
+}
+
+impl Statistic {
+
+    /// Returns the value of the statistic in the given sample, or None, if the sample lacks it,
+    /// e.g. because a village has been founded afterwards
+    /// # Arguments
+    /// * `sample` is the sample, from which the value is taken
+    fn value(&self, sample: &Sample) -> Option<i64> {
+        match *self {
+            Statistic::Treasury => Some(sample.treasury as i64),
+            Statistic::TotalCitizens => Some(sample.citizens.iter().map(|c| *c as i64).sum()),
+            Statistic::Citizens(village) => sample.citizens.get(village).map(|c| *c as i64),
+            Statistic::TotalCraftLevels => Some(sample.craft_levels.iter().map(|(_, level)| *level as i64).sum()),
+            Statistic::CraftLevels(craft_type) => sample.craft_level(craft_type).map(|level| level as i64),
+        }
+    }
+}
+
+/// A view, which plots the history of the kingdom's statistics. Sparklines give an overview of the
+/// treasury, the citizens and the crafts, while a chart plots the selected statistic in detail. The
+/// time window can be switched like a filter. The scrolling offset selects one of the statistics.
+pub struct StatisticsMenu{
+    /// The scrolling offset, which equals the index of the selected statistic
+    scroll_offset: u16,
+    /// The index of the time window, which is plotted
+    window: usize,
+    /// The number of statistics, which have been listed in the last rendering
+    num_entries: usize,
+    /// The area of the list of statistics in the last rendering
+    list_area: Rect,
+    /// The areas, which the view has covered in its last rendering
+    layout: ViewLayout,
+}
+
+impl StatisticsMenu{
+    /// Initialize StatisticsMenu with a scrolling offset of 0 and the shortest time window
+    pub fn new() -> StatisticsMenu{
+        StatisticsMenu{
+            scroll_offset: 0,
+            window: 0,
+            num_entries: 0,
+            list_area: Rect::default(),
+            layout: ViewLayout::default(),
+        }
+    }
+
+    /// Returns the label of the current time window
+    fn window_label(&self) -> String{
+        match TIME_WINDOWS[self.window] {
+            Some(days) => format!("Last {days} days"),
+            None => String::from("All"),
+        }
+    }
+
+    /// Returns every sample of the history, which lies within the current time window
+    /// # Arguments
+    /// * `statistics` is the history of the kingdom's statistics
+    fn samples_in_window<'a>(&self, statistics: &'a StatisticsHistory) -> Vec<&'a Sample>{
+
+        let latest_tick = statistics.latest().map(|sample| sample.tick).unwrap_or_default();
+
+        let first_tick = match TIME_WINDOWS[self.window] {
+            Some(days) => latest_tick.saturating_sub(days - 1),
+            None => 0,
+        };
+
+        statistics.since(first_tick).collect()
+    }
+}
+
+impl<B: Backend> TerminalRenderer<B> for StatisticsMenu{
+
+    /// Draws the sparklines of the overview, the list of statistics and the chart of the selected one
+    fn render(&mut self, frame: &mut Frame<B>, menu : &ViewRegistry, game_state: &GameState, theme: &Theme) {
+
+        let main_style : Style = theme.main_style();
+        let highlight_style : Style = theme.highlight_style();
+
+        let chunks = split_view(frame.size(), menu);
+
+        let kingdom = &game_state.kingdom;
+        let samples = self.samples_in_window(&game_state.statistics);
+
+        let mut statistics = vec![(String::from("Treasury"), Statistic::Treasury)];
+        statistics.extend(kingdom.villages.iter().enumerate()
+            .map(|(i, v)| (format!("Citizens of {}", v.name), Statistic::Citizens(i))));
+        statistics.extend(kingdom.crafts.keys()
+            .map(|craft_type| (format!("{craft_type} levels"), Statistic::CraftLevels(*craft_type))));
+
+        // The selection can't move beyond the last statistic
+        self.scroll_offset = self.scroll_offset.min(statistics.len().saturating_sub(1) as u16);
+
+        let view_block = Block::default()
+            .borders(Borders::ALL)
+            .style(main_style)
+            .title(format!("{} ({}) - [F]ilter", menu.breadcrumbs(), self.window_label()));
+
+        let areas = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(OVERVIEW_HEIGHT), Constraint::Min(0)].as_ref())
+            .split(view_block.inner(chunks[1]));
+
+        frame.render_widget(view_block, chunks[1]);
+
+        // The overview plots the trends of the whole kingdom
+        let overview = [
+            ("Treasury", Statistic::Treasury),
+            ("Citizens", Statistic::TotalCitizens),
+            ("Craft levels", Statistic::TotalCraftLevels),
+        ];
+
+        let overview_areas = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Ratio(1, 3), Constraint::Ratio(1, 3), Constraint::Ratio(1, 3)].as_ref())
+            .split(areas[0]);
+
+        for ((label, statistic), area) in overview.iter().zip(overview_areas) {
+
+            let values: Vec<i64> = samples.iter().filter_map(|s| statistic.value(s)).collect();
+            let data = sparkline_data(&values, area.width.saturating_sub(2) as usize);
+
+            let title = match values.last() {
+                Some(value) => format!("{label}: {value}"),
+                None => String::from(*label),
+            };
+
+            let sparkline = Sparkline::default()
+                .block(Block::default().borders(Borders::ALL).title(title))
+                .style(main_style)
+                .data(&data);
+
+            frame.render_widget(sparkline, area);
+        }
+
+        let detail_areas = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(SERIES_LIST_WIDTH), Constraint::Min(0)].as_ref())
+            .split(areas[1]);
+
+        let items: Vec<ListItem> = statistics.iter()
+            .map(|(label, _)| ListItem::new(label.as_str()))
+            .collect();
+
+        let statistics_list = List::new(items)
+            .style(main_style)
+            .highlight_style(highlight_style)
+            .highlight_symbol("> ")
+            .block(Block::default().borders(Borders::ALL).title("Statistic"));
+
+        let mut state = ListState::default();
+        state.select(Some(self.scroll_offset as usize));
+
+        frame.render_stateful_widget(statistics_list, detail_areas[0], &mut state);
+
+        let (label, statistic) = &statistics[self.scroll_offset as usize];
+        let points: Vec<(f64, f64)> = samples.iter()
+            .filter_map(|s| statistic.value(s).map(|value| (s.tick as f64, value as f64)))
+            .collect();
+
+        if points.is_empty() {
+
+            let no_data = Paragraph::new("No statistics have been recorded yet")
+                .style(main_style)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::ALL).title(label.as_str()));
+
+            frame.render_widget(no_data, detail_areas[1]);
+        } else {
+
+            let x_bounds = bounds(points.iter().map(|(x, _)| *x));
+            let y_bounds = bounds(points.iter().map(|(_, y)| *y));
+
+            let dataset = Dataset::default()
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(main_style)
+                .data(&points);
+
+            let chart = Chart::new(vec![dataset])
+                .style(main_style)
+                .block(Block::default().borders(Borders::ALL).title(format!("{label}: {}", points[points.len() - 1].1)))
+                .x_axis(Axis::default()
+                    .bounds(x_bounds)
+                    .labels(vec![Span::raw(format!("Day {}", x_bounds[0])), Span::raw(format!("Day {}", x_bounds[1]))]))
+                .y_axis(Axis::default()
+                    .bounds(y_bounds)
+                    .labels(vec![Span::raw(y_bounds[0].to_string()), Span::raw(y_bounds[1].to_string())]));
+
+            frame.render_widget(chart, detail_areas[1]);
+        }
+
+        self.num_entries = statistics.len();
+        self.list_area = detail_areas[0];
+        self.layout = ViewLayout { menu: chunks[0], content: chunks[1] };
+
+        // The scrollbar is only needed, if not every statistic fits into the list
+        if self.num_entries > page_height(self.list_area) as usize {
+            Scrollbar::new(self.scroll_offset, self.num_entries.saturating_sub(1) as u16).render(frame, self.list_area, theme);
+        }
+
+        menu.render(frame, chunks[0], theme);
+    }
+
+    fn set_scroll_offset(&mut self, offset: u16) {
+        self.scroll_offset = offset;
+    }
+
+    fn get_scroll_offset(&self) -> u16 {
+        self.scroll_offset
+    }
+
+    fn get_max_scroll_offset(&self) -> u16 {
+        self.num_entries.saturating_sub(1) as u16
+    }
+
+    fn get_page_height(&self) -> u16 {
+        page_height(self.list_area)
+    }
+
+    fn get_layout(&self) -> ViewLayout {
+        self.layout
+    }
+
+    /// Cycles through the time windows from the shortest to the whole history and back
+    fn cycle_filter(&mut self) {
+        self.window = (self.window + 1) % TIME_WINDOWS.len();
+    }
+
+    fn select_at(&mut self, row: u16) {
+        if let Some(index) = list_index_at(self.list_area, row, self.scroll_offset as usize, self.num_entries) {
+            self.scroll_offset = index as u16;
+        }
+    }
+}
+
+/// Returns the data of a sparkline, which fits into the given width. As a sparkline can't display
+/// negative values, every value is plotted relative to the smallest one. If there are more values
+/// than columns, only every n-th value is plotted, but the most recent one in any case.
+/// # Arguments
+/// * `values` are the values, the oldest one first
+/// * `width` is the number of columns, which are available
+fn sparkline_data(values: &[i64], width: usize) -> Vec<u64> {
+
+    let min = values.iter().copied().min().unwrap_or_default();
+    let step = values.len().div_ceil(width.max(1)).max(1);
+
+    values.iter()
+        .rev()
+        .step_by(step)
+        .map(|value| (value - min) as u64)
+        .collect::<Vec<u64>>()
+        .into_iter()
+        .rev()
+        .collect()
+}
+
+/// Returns the bounds of an axis, which covers every given value. If every value is the same, the
+/// bounds are widened, so that the values are plotted in the middle.
+/// # Arguments
+/// * `values` are the values, which are plotted along the axis
+fn bounds(values: impl Iterator<Item = f64>) -> [f64; 2] {
+
+    let (min, max) = values.fold((f64::MAX, f64::MIN), |(min, max), value| (min.min(value), max.max(value)));
+
+    if min < max {
+        [min, max]
+    } else {
+        [min - 1.0, max + 1.0]
+    }
+}
@@ -0,0 +1,56 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// The maximum number of characters, which can be typed into a text input
+const MAX_TEXT_LENGTH: usize = 32;
+
+/// A single line of text, which the player can type into
+pub struct TextInput{
+    /// The typed text
+    text: String,
+}
+
+impl TextInput{
+
+    /// Creates an empty text input
+    pub fn new() -> TextInput{
+        TextInput{
+            text: String::new(),
+        }
+    }
+
+    /// Returns the typed text
+    pub fn get_text(&self) -> &str{
+        &self.text
+    }
+
+    /// Removes the typed text
+    pub fn clear(&mut self){
+        self.text.clear();
+    }
+
+    /// Appends a typed character or removes the last one for a backspace. Returns true, if the key
+    /// event has been handled. Any other key event, e.g. a key combined with Ctrl, is left to the
+    /// key bindings.
+    /// # Arguments
+    /// * `key_event` is the key event, which has been typed
+    pub fn handle_key_event(&mut self, key_event: &KeyEvent) -> bool{
+
+        if key_event.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
+            return false;
+        }
+
+        match key_event.code {
+            KeyCode::Char(c) => {
+                if self.text.chars().count() < MAX_TEXT_LENGTH {
+                    self.text.push(c);
+                }
+                true
+            }
+            KeyCode::Backspace => {
+                self.text.pop();
+                true
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::Style,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use std::collections::VecDeque;
+use std::ops::Add;
+use std::time::{Duration, Instant};
+use crate::game::messages::Message;
+use crate::menu::themes::Theme;
+
+/// The number of toasts, which are displayed at the same time
+const MAX_VISIBLE_TOASTS: usize = 3;
+
+/// The duration, for which a toast is displayed
+const TOAST_DURATION: Duration = Duration::from_secs(5);
+
+/// An overlay, which briefly displays new messages in the lower right corner of the terminal
+pub struct Toasts{
+    /// The displayed messages together with their expiration times, the oldest one first
+    toasts: VecDeque<(Message, Instant)>,
+}
+
+impl Toasts{
+
+    /// Creates an overlay without any toasts
+    pub fn new() -> Toasts{
+        Toasts{
+            toasts: VecDeque::new(),
+        }
+    }
+
+    /// Adds a toast for a new message. If there are too many toasts, the oldest one is dropped.
+    /// # Arguments
+    /// * `message` is the new message
+    pub fn push(&mut self, message: Message){
+
+        if self.toasts.len() == MAX_VISIBLE_TOASTS {
+            self.toasts.pop_front();
+        }
+
+        self.toasts.push_back((message, Instant::now().add(TOAST_DURATION)));
+    }
+
+    /// Removes every toast, whose expiration time lies in the past. Returns true, if any toast has
+    /// been removed.
+    pub fn remove_expired(&mut self) -> bool{
+        let now = Instant::now();
+        let len = self.toasts.len();
+        self.toasts.retain(|(_, expiration_time)| expiration_time.gt(&now));
+        self.toasts.len() != len
+    }
+
+    /// Returns the time, at which the oldest toast expires, or None, if there is none
+    pub fn next_expiration_time(&self) -> Option<Instant>{
+        self.toasts.front().map(|(_, expiration_time)| *expiration_time)
+    }
+
+    /// Draws the toasts on top of each other in the lower right corner of the frame, the newest
+    /// one at the bottom
+    /// # Arguments
+    /// * `frame` is the terminal's frame in which the toasts shall be drawn
+    /// * `theme` is the theme, whose colors are used
+    pub fn render<B: Backend>(&self, frame: &mut Frame<B>, theme: &Theme){
+
+        let toast_style : Style = theme.highlight_style();
+
+        let size = frame.size();
+        let width = (size.width / 3).max(20).min(size.width);
+        let height = 4;
+
+        for (i, (message, _)) in self.toasts.iter().rev().enumerate() {
+
+            let offset = height * (i as u16 + 1);
+
+            if offset > size.height {
+                break;
+            }
+
+            let area = Rect::new(size.width - width, size.height - offset, width, height);
+
+            let toast = Paragraph::new(message.text.as_str())
+                .style(toast_style)
+                .wrap(Wrap { trim: true })
+                .block(Block::default().borders(Borders::ALL).title(format!("Day {} - {}", message.tick, message.category)));
+
+            frame.render_widget(Clear, area);
+            frame.render_widget(toast, area);
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use tui::{
+    backend::Backend,
+    layout::Alignment,
+    style::Style,
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::game::GameState;
+use crate::menu::view_registry::ViewRegistry;
+use crate::menu::rendering::{split_view, TerminalRenderer, ViewLayout};
+use crate::menu::themes::Theme;
+
+/// A view, which displays the details of a single village. It is opened from the village view.
+pub struct VillageDetailMenu{
+    /// The index of the displayed village
+    village: usize,
+    /// The areas, which the view has covered in its last rendering
+    layout: ViewLayout,
+}
+
+impl VillageDetailMenu{
+    /// Initialize VillageDetailMenu for the first village
+    pub fn new() -> VillageDetailMenu{
+        VillageDetailMenu{
+            village: 0,
+            layout: ViewLayout::default(),
+        }
+    }
+}
+
+impl<B: Backend> TerminalRenderer<B> for VillageDetailMenu{
+
+    /// Draws the details of the village to the terminal
+    fn render(&mut self, frame: &mut Frame<B>, menu : &ViewRegistry, game_state: &GameState, theme: &Theme) {
+
+        let main_style : Style = theme.main_style();
+
+        let chunks = split_view(frame.size(), menu);
+
+        let details = match game_state.kingdom.villages.get(self.village) {
+            Some(village) => format!("{}\n\nCitizens: {}\n\n[Enter] Rename", village.name, village.num_citizen),
+            None => String::from("This village does not exist anymore."),
+        };
+
+        let detail_screen = Paragraph::new(details)
+            .style(main_style)
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::ALL).title(menu.breadcrumbs()));
+
+        frame.render_widget(detail_screen, chunks[1]);
+
+        menu.render(frame, chunks[0], theme);
+
+        self.layout = ViewLayout { menu: chunks[0], content: chunks[1] };
+    }
+
+    fn set_scroll_offset(&mut self, _offset: u16) {
+        // nothing to do
+    }
+
+    fn get_scroll_offset(&self) -> u16 {
+        0
+    }
+
+    fn get_layout(&self) -> ViewLayout {
+        self.layout
+    }
+
+    /// Passes the displayed village on to the rename view
+    fn get_selection(&self) -> usize {
+        self.village
+    }
+
+    fn set_parent_selection(&mut self, selection: usize) {
+        self.village = selection;
+    }
+}
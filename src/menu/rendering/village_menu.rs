@@ -1,21 +1,26 @@
 use tui::{
-    backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout, Alignment},
-    style::{Color, Style},
-    widgets::{Block, Borders, List, Paragraph},
-    Terminal,
+    backend::Backend,
+    style::Style,
+    widgets::{Block, Borders, List, ListItem, ListState},
+    Frame,
 };
 
-use std::io::Stdout;
-use std::sync::{Arc,RwLock};
 use crate::data::TerminalDisplay;
 use crate::game::GameState;
-use crate::menu::rendering::TerminalRenderer;
+use crate::menu::view_registry::ViewRegistry;
+use crate::menu::rendering::{list_index_at, page_height, split_view, TerminalRenderer, ViewLayout};
+use crate::menu::rendering::scrollbar::Scrollbar;
+use crate::menu::themes::Theme;
 
-/// A view, which displays a player's villages
+/// A view, which displays a player's villages. The scrolling offset selects one of the villages,
+/// whose details may be opened.
 pub struct VillageMenu{
-    /// The scrolling offset in case of bigger menues
+    /// The scrolling offset, which equals the index of the selected village
     scroll_offset: u16,
+    /// The number of villages, which have been displayed in the last rendering
+    num_entries: usize,
+    /// The areas, which the view has covered in its last rendering
+    layout: ViewLayout,
 }
 
 impl VillageMenu{
@@ -23,37 +28,51 @@ impl VillageMenu{
     pub fn new() -> VillageMenu{
         VillageMenu{
             scroll_offset: 0,
+            num_entries: 0,
+            layout: ViewLayout::default(),
         }
     }
 }
 
-impl TerminalRenderer for VillageMenu{
+impl<B: Backend> TerminalRenderer<B> for VillageMenu{
 
     /// Draws the village view to the terminal
-    fn render(&mut self, terminal: &mut Terminal<CrosstermBackend<Stdout>>, menu_widget : &List, game_state: Arc<RwLock<GameState>>) {
+    fn render(&mut self, frame: &mut Frame<B>, menu : &ViewRegistry, game_state: &GameState, theme: &Theme) {
 
-        let main_style : Style = Style::default().bg(Color::Rgb(50, 25, 0));
+        let main_style : Style = theme.main_style();
+        let highlight_style : Style = theme.highlight_style();
 
-        terminal.draw(|f| {
-            let chunks = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints([Constraint::Percentage(15), Constraint::Percentage(85)].as_ref())
-                .split(f.size());
+        let chunks = split_view(frame.size(), menu);
 
-            let villages: String = game_state.read().unwrap().kingdom.villages.iter()
-                .map(|v| v.display())
-                .collect();
+        let villages = &game_state.kingdom.villages;
 
-            let village_screen = Paragraph::new(format!("Your villages:\n {}", villages))
-                .style(main_style)
-                .alignment(Alignment::Center)
-                .scroll((self.get_scroll_offset(),0))
-                .block(Block::default().borders(Borders::ALL).title("Main"));
+        // The selection can't move beyond the last village
+        self.scroll_offset = self.scroll_offset.min(villages.len().saturating_sub(1) as u16);
 
-            f.render_widget(village_screen, chunks[1]);
+        let items: Vec<ListItem> = villages.iter()
+            .map(|v| ListItem::new(v.display().trim_end().to_string()))
+            .collect();
 
-            f.render_widget(menu_widget.clone(), chunks[0]);
-        }).expect("Can render widget");
+        let village_screen = List::new(items)
+            .style(main_style)
+            .highlight_style(highlight_style)
+            .highlight_symbol("> ")
+            .block(Block::default().borders(Borders::ALL).title(menu.breadcrumbs()));
+
+        let mut state = ListState::default();
+        state.select((!villages.is_empty()).then_some(self.scroll_offset as usize));
+
+        frame.render_stateful_widget(village_screen, chunks[1], &mut state);
+
+        self.num_entries = villages.len();
+        self.layout = ViewLayout { menu: chunks[0], content: chunks[1] };
+
+        // The scrollbar is only needed, if not every village fits into the view
+        if self.num_entries > page_height(self.layout.content) as usize {
+            Scrollbar::new(self.scroll_offset, self.num_entries.saturating_sub(1) as u16).render(frame, chunks[1], theme);
+        }
+
+        menu.render(frame, chunks[0], theme);
     }
 
     fn set_scroll_offset(&mut self, offset: u16) {
@@ -63,4 +82,18 @@ impl TerminalRenderer for VillageMenu{
     fn get_scroll_offset(&self) -> u16 {
         self.scroll_offset
     }
+
+    fn get_max_scroll_offset(&self) -> u16 {
+        self.num_entries.saturating_sub(1) as u16
+    }
+
+    fn get_layout(&self) -> ViewLayout {
+        self.layout
+    }
+
+    fn select_at(&mut self, row: u16) {
+        if let Some(index) = list_index_at(self.layout.content, row, self.scroll_offset as usize, self.num_entries) {
+            self.scroll_offset = index as u16;
+        }
+    }
 }
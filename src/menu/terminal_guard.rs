@@ -0,0 +1,114 @@
+use crossterm::{
+    cursor::{Hide, Show},
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use signal_hook::consts::{SIGINT, SIGTERM};
+use signal_hook::iterator::Signals;
+
+use std::io;
+use std::panic;
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether the terminal is currently in raw mode and shows the alternate screen
+static TERMINAL_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// The exit code, which is used if any thread panics. It equals the exit code of Rust's default
+/// panic handling.
+const PANIC_EXIT_CODE: i32 = 101;
+
+/// A guard, which puts the terminal into raw mode, switches to the alternate screen and captures the
+/// mouse. The terminal is restored to its previous state, when the guard is dropped, when any thread
+/// panics or when the process receives SIGINT or SIGTERM.
+pub struct TerminalGuard{}
+
+impl TerminalGuard{
+
+    /// Enters raw mode and the alternate screen, enables mouse capture and installs the panic hook and the signal handler,
+    /// which restore the terminal
+    pub fn new() -> io::Result<TerminalGuard>{
+
+        install_panic_hook();
+        install_signal_handler()?;
+
+        enable_raw_mode()?;
+        TERMINAL_ACTIVE.store(true, Ordering::SeqCst);
+        execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture, Hide)?;
+
+        Ok(TerminalGuard{})
+    }
+}
+
+impl Drop for TerminalGuard{
+
+    /// Restores the terminal, when the application exits normally
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+/// Disables mouse capture and leaves the alternate screen and raw mode, if the terminal is still active. Calling this function
+/// more than once has no further effect.
+pub fn restore_terminal(){
+
+    if TERMINAL_ACTIVE.swap(false, Ordering::SeqCst) {
+        // The terminal is restored on a best-effort basis, since there is nobody left to report to
+        let _ = execute!(io::stdout(), DisableMouseCapture, LeaveAlternateScreen, Show);
+        let _ = disable_raw_mode();
+    }
+}
+
+/// Installs a panic hook, which restores the terminal before the panic message is printed. Since
+/// the other threads can't continue without the panicked one, the process exits afterwards.
+fn install_panic_hook(){
+
+    let default_hook = panic::take_hook();
+
+    panic::set_hook(Box::new(move |panic_info| {
+        log::error!(panic:% = panic_info; "Thread panicked");
+        restore_terminal();
+        default_hook(panic_info);
+        process::exit(PANIC_EXIT_CODE);
+    }));
+}
+
+/// Starts a thread, which restores the terminal and exits the process upon receiving SIGINT or
+/// SIGTERM. The exit code follows the shell convention of 128 plus the signal's number.
+fn install_signal_handler() -> io::Result<()>{
+
+    let mut signals = Signals::new([SIGINT, SIGTERM])?;
+
+    crate::spawn_thread("signals", move || {
+        if let Some(signal) = signals.forever().next() {
+            restore_terminal();
+            process::exit(128 + signal);
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    // Entering raw mode needs a terminal, which the tests don't have. Hence, only restoring the
+    // terminal is tested, which must be safe to call from the guard, the panic hook and the signal
+    // handler alike.
+    #[test]
+    fn the_terminal_is_only_restored_once() {
+
+        restore_terminal();
+        assert!(!TERMINAL_ACTIVE.load(Ordering::SeqCst));
+
+        TERMINAL_ACTIVE.store(true, Ordering::SeqCst);
+        drop(TerminalGuard{});
+        assert!(!TERMINAL_ACTIVE.load(Ordering::SeqCst));
+
+        restore_terminal();
+        assert!(!TERMINAL_ACTIVE.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,246 @@
+use std::env;
+use std::fs;
+
+use serde::Deserialize;
+use tui::style::{Color, Modifier, Style};
+
+use crate::menu::assets;
+use crate::menu::ui_foundations::UIError;
+
+/// The name of the themes file, both in the asset-folder and the user's config directory
+pub const THEMES_FILE: &str = "themes.json";
+
+/// A named set of colors, which every view and overlay is drawn with. Every color, which a theme
+/// leaves out, is taken from the default theme, but misspelt colors are rejected.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase", default, deny_unknown_fields)]
+pub struct Theme {
+    /// The name of the theme, which is displayed in the settings
+    pub name: String,
+    /// The background color of every view
+    background: Color,
+    /// The text color of every view
+    foreground: Color,
+    /// The background color of selected entries, dialogs' choices and toasts
+    highlight: Color,
+    /// The text color of highlighted entries
+    highlight_foreground: Color,
+    /// The background color of the menu
+    menu: Color,
+    /// The background color of the current view's entry in the menu
+    menu_highlight: Color,
+    /// The background color of the error banner
+    error: Color,
+    /// The text color of the error banner
+    error_foreground: Color,
+    /// Whether highlighted entries and errors are displayed in reverse video. This allows themes
+    /// to work without any colors.
+    reverse_highlight: bool,
+}
+
+impl Default for Theme {
+
+    /// Returns a nameless theme with the 16 colors, which every terminal supports
+    fn default() -> Self {
+        Theme {
+            name: String::new(),
+            background: Color::Black,
+            foreground: Color::White,
+            highlight: Color::Yellow,
+            highlight_foreground: Color::Black,
+            menu: Color::Red,
+            menu_highlight: Color::Yellow,
+            error: Color::Red,
+            error_foreground: Color::White,
+            reverse_highlight: false,
+        }
+    }
+}
+
+impl Theme {
+
+    /// Returns the style of every view's content
+    pub fn main_style(&self) -> Style {
+        Style::default().bg(self.background).fg(self.foreground)
+    }
+
+    /// Returns the style of selected entries, dialogs' choices and toasts
+    pub fn highlight_style(&self) -> Style {
+        self.reversed(Style::default().bg(self.highlight).fg(self.highlight_foreground))
+    }
+
+    /// Returns the style of the menu
+    pub fn menu_style(&self) -> Style {
+        Style::default().bg(self.menu).fg(self.foreground)
+    }
+
+    /// Returns the style of the current view's entry in the menu
+    pub fn menu_highlight_style(&self) -> Style {
+        self.reversed(Style::default().bg(self.menu_highlight).fg(self.highlight_foreground))
+            .add_modifier(Modifier::BOLD)
+    }
+
+    /// Returns the style of the error banner
+    pub fn error_style(&self) -> Style {
+        self.reversed(Style::default().bg(self.error).fg(self.error_foreground))
+    }
+
+    /// Returns true, if the theme uses any RGB-color, which can only be displayed by terminals
+    /// supporting true color
+    pub fn uses_true_color(&self) -> bool {
+        [self.background, self.foreground, self.highlight, self.highlight_foreground, self.menu,
+            self.menu_highlight, self.error, self.error_foreground]
+            .iter()
+            .any(|c| matches!(c, Color::Rgb(..)))
+    }
+
+    /// Adds reverse video to the style, if the theme requires it
+    /// # Arguments
+    /// * `style` is the style, which may be reversed
+    fn reversed(&self, style: Style) -> Style {
+        if self.reverse_highlight {
+            style.add_modifier(Modifier::REVERSED)
+        } else {
+            style
+        }
+    }
+}
+
+/// The set of themes, which the player can choose from in the settings. They are read from the
+/// file 'themes.json', either from the user's config directory or, if there is none, from the
+/// asset-folder.
+pub struct Themes {
+    /// Every theme in the order of the themes file
+    themes: Vec<Theme>,
+}
+
+impl Themes {
+
+    /// Loads the themes. A file 'railan/themes.json' in the user's config directory takes
+    /// precedence over the one in the asset-folder.
+    pub fn load() -> Result<Themes, UIError> {
+
+        let json = match assets::user_config_path(THEMES_FILE).filter(|p| p.is_file()) {
+            Some(path) => fs::read_to_string(&path)
+                .map_err(|e| UIError::InvalidThemes(format!("Unable to read '{}': {e}", path.display())))?,
+            None => assets::read_asset(THEMES_FILE).map_err(UIError::Asset)?,
+        };
+
+        Themes::parse(&json)
+    }
+
+    /// Parses and validates the JSON-content of a themes file. The themes are invalid, if there
+    /// is none, if a theme has no name or if two of them share the same name.
+    /// # Arguments
+    /// * `json` is the content of the themes file
+    pub fn parse(json: &str) -> Result<Themes, UIError> {
+
+        let themes: Vec<Theme> = serde_json::from_str(json)
+            .map_err(|e| UIError::InvalidThemes(format!("The file is not well-formatted: {e}")))?;
+
+        if themes.is_empty() {
+            return Err(UIError::InvalidThemes(String::from("There must be at least one theme")));
+        }
+
+        for (i, theme) in themes.iter().enumerate() {
+            if theme.name.is_empty() {
+                return Err(UIError::InvalidThemes(format!("The theme at position {} has no name", i + 1)));
+            }
+            if themes[..i].iter().any(|t| t.name == theme.name) {
+                return Err(UIError::InvalidThemes(format!("The name '{}' is used by more than one theme", theme.name)));
+            }
+        }
+
+        Ok(Themes { themes })
+    }
+
+    /// Returns the theme at the given index. If there is no such theme, the first one is returned.
+    /// # Arguments
+    /// * `index` is the index of the theme
+    pub fn get(&self, index: usize) -> &Theme {
+        self.themes.get(index).unwrap_or(&self.themes[0])
+    }
+
+    /// Returns the names of every theme in the order of the themes file
+    pub fn names(&self) -> Vec<String> {
+        self.themes.iter().map(|t| t.name.clone()).collect()
+    }
+
+    /// Returns the index of the theme, which is used at startup. It is the first theme, unless the
+    /// terminal does not announce true color support via $COLORTERM. In that case, the first theme
+    /// without RGB-colors is preferred.
+    pub fn default_index(&self) -> usize {
+
+        let supports_true_color = env::var("COLORTERM")
+            .map(|c| c == "truecolor" || c == "24bit")
+            .unwrap_or(false);
+
+        if supports_true_color {
+            0
+        } else {
+            self.themes.iter().position(|t| !t.uses_true_color()).unwrap_or(0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /// Returns the message of the error, which parsing the given themes leads to
+    /// # Arguments
+    /// * `json` is the content of a themes file
+    fn parse_error(json: &str) -> String {
+        match Themes::parse(json) {
+            Ok(_) => panic!("The themes should be invalid"),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    #[test]
+    fn unknown_colors_are_rejected() {
+
+        let error = parse_error(r#"[{ "Name": "Purple", "Background": "Purple" }]"#);
+
+        assert!(error.starts_with("Invalid themes: The file is not well-formatted: unknown variant `Purple`"), "{error}");
+    }
+
+    #[test]
+    fn unknown_styles_are_rejected() {
+
+        let error = parse_error(r#"[{ "Name": "Typo", "Highlite": "Yellow" }]"#);
+
+        assert!(error.starts_with("Invalid themes: The file is not well-formatted: unknown field `Highlite`"), "{error}");
+    }
+
+    #[test]
+    fn a_partial_theme_falls_back_to_the_default_colors() {
+
+        let themes = Themes::parse(r#"[{ "Name": "Blue", "Background": "Blue" }]"#).unwrap();
+        let theme = themes.get(0);
+        let default = Theme::default();
+
+        assert_eq!(theme.main_style(), Style::default().bg(Color::Blue).fg(default.foreground));
+        assert_eq!(theme.highlight_style(), default.highlight_style());
+        assert_eq!(theme.menu_style(), default.menu_style());
+        assert_eq!(theme.error_style(), default.error_style());
+        assert!(!theme.uses_true_color());
+    }
+
+    #[test]
+    fn themes_need_a_unique_name() {
+
+        assert_eq!(parse_error(r#"[{ "Background": "Blue" }]"#), "Invalid themes: The theme at position 1 has no name");
+        assert_eq!(parse_error(r#"[{ "Name": "A" }, { "Name": "A" }]"#), "Invalid themes: The name 'A' is used by more than one theme");
+        assert_eq!(parse_error("[]"), "Invalid themes: There must be at least one theme");
+    }
+
+    #[test]
+    fn the_default_themes_are_valid() {
+
+        let themes = Themes::parse(&assets::read_asset(THEMES_FILE).unwrap()).unwrap();
+
+        assert!(themes.names().contains(&String::from("Monochrome")));
+    }
+}
@@ -1,6 +1,9 @@
 use std::cell::{RefCell};
+use std::fmt::{Display, Formatter};
 use std::rc::Rc;
 use std::io::Stdout;
+use serde::Deserialize;
+use crate::menu::assets::AssetError;
 use tui::{
     backend::CrosstermBackend,
     Terminal,
@@ -8,34 +11,137 @@ use tui::{
 use std::io;
 
 /// UIType contains a set of view-types, which can be displayed in the terminal
-#[derive(Debug,Eq,PartialEq,Copy, Clone,Hash)]
+#[derive(Debug,Eq,PartialEq,Copy, Clone,Hash,Deserialize)]
 pub enum UIType{
     Start,
     Main,
     Villages,
     Crafts,
     Diplomacy,
+    MessageLog,
+    Statistics,
+    VillageDetail,
+    RenameVillage,
+    Settings,
     Quit,
     Terminated,
 }
 
-/// An enum denoting scrolling-directions in a menu for arrow up/down input
-#[derive(Copy,Clone,Debug)]
+impl UIType{
+
+    /// Returns the title of the view, which is e.g. displayed in the menu
+    pub fn title(&self) -> &'static str{
+        match *self{
+            UIType::Start => "Start",
+            UIType::Main => "Main",
+            UIType::Villages => "Villages",
+            UIType::Crafts => "Crafts",
+            UIType::Diplomacy => "Diplomacy",
+            UIType::MessageLog => "Messages",
+            UIType::Statistics => "Statistics",
+            UIType::VillageDetail => "Village",
+            UIType::RenameVillage => "Rename",
+            UIType::Settings => "Settings",
+            UIType::Quit => "Quit",
+            UIType::Terminated => "Terminated",
+        }
+    }
+
+    /// Returns true, if the content of the view can be scrolled
+    pub fn is_scrollable(&self) -> bool{
+        matches!(*self, UIType::Villages | UIType::Crafts | UIType::Diplomacy | UIType::MessageLog | UIType::Statistics | UIType::Settings)
+    }
+
+    /// Returns true, if the content of the view can be filtered
+    pub fn is_filterable(&self) -> bool{
+        matches!(*self, UIType::MessageLog | UIType::Statistics)
+    }
+
+    /// Returns true, if the view can be opened directly by a key. Nested views can only be opened
+    /// from their parent view, as they depend on its selection, e.g. the detail view of a village.
+    pub fn is_directly_accessible(&self) -> bool{
+        !matches!(*self, UIType::Start | UIType::Terminated | UIType::VillageDetail | UIType::RenameVillage)
+    }
+}
+
+/// An enum denoting scrolling-directions in a menu for arrow up/down, page up/down and home/end input
+#[derive(Copy,Clone,Debug,Deserialize)]
 pub enum ScrollingDirection{
     /// Upwards scrolling
     Up,
     /// Downwards scrolling
     Down,
+    /// Upwards scrolling by a page
+    PageUp,
+    /// Downwards scrolling by a page
+    PageDown,
+    /// Scrolling to the beginning
+    Home,
+    /// Scrolling to the end
+    End,
 }
 
-/// ActionType contains a set of different actions, which can be executed in a ui
+/// ActionType contains a set of different actions, which can be executed in a ui. Apart from
+/// HandleUITimeout, every action can be bound to a key in the key bindings.
+#[derive(Copy, Clone, Debug, Deserialize)]
 pub enum ActionType{
     /// An action to change the current view, wherein the argument refers to the new view type
     ChangeView(UIType),
     /// An action to handle a timeout event for a view, wherein the argument refers to the new view type
     HandleUITimeout(UIType),
     /// Ac action to scroll down, if the argument is true, scroll up otherwise
-    Scroll(ScrollingDirection)
+    Scroll(ScrollingDirection),
+    /// An action to confirm the selected option of a dialog
+    Confirm,
+    /// An action to switch to the next filter of the current view
+    CycleFilter,
+    /// An action to show or hide the help, which lists every key binding
+    ToggleHelp,
+    /// An action to cancel the current dialog or to return to the previous view
+    Back,
+    /// An action to save the game to its save file
+    Save,
+    /// An action to declare war on the selected neighbour
+    DeclareWar,
+}
+
+impl ActionType{
+
+    /// Returns a description of the action for the help, which depends on the current view. If the
+    /// action has no effect in the current view, None is returned.
+    /// # Arguments
+    /// * `ui_type` is the type of the current view
+    pub fn description(&self, ui_type: UIType) -> Option<String>{
+        match *self{
+            ActionType::ChangeView(UIType::Quit) => Some(String::from("Quit the game")),
+            ActionType::ChangeView(view) => Some(format!("Open {}", view.title())),
+            ActionType::HandleUITimeout(_) => None,
+            ActionType::Scroll(ScrollingDirection::Up) if ui_type.is_scrollable() => Some(String::from("Scroll up or select the previous choice of a dialog")),
+            ActionType::Scroll(ScrollingDirection::Down) if ui_type.is_scrollable() => Some(String::from("Scroll down or select the next choice of a dialog")),
+            ActionType::Scroll(ScrollingDirection::Up) => Some(String::from("Select the previous choice of a dialog")),
+            ActionType::Scroll(ScrollingDirection::Down) => Some(String::from("Select the next choice of a dialog")),
+            ActionType::Scroll(ScrollingDirection::PageUp) if ui_type.is_scrollable() => Some(String::from("Scroll up by a page")),
+            ActionType::Scroll(ScrollingDirection::PageDown) if ui_type.is_scrollable() => Some(String::from("Scroll down by a page")),
+            ActionType::Scroll(ScrollingDirection::Home) if ui_type.is_scrollable() => Some(String::from("Scroll to the beginning")),
+            ActionType::Scroll(ScrollingDirection::End) if ui_type.is_scrollable() => Some(String::from("Scroll to the end")),
+            ActionType::Scroll(_) => None,
+            ActionType::Confirm => match ui_type {
+                UIType::Villages => Some(String::from("Show the selected village")),
+                UIType::VillageDetail => Some(String::from("Rename the village")),
+                UIType::RenameVillage => Some(String::from("Confirm the new name")),
+                UIType::Settings => Some(String::from("Apply the selected theme")),
+                _ => Some(String::from("Confirm the selected choice of a dialog")),
+            },
+            ActionType::CycleFilter if ui_type == UIType::Statistics => Some(String::from("Switch to the next time window")),
+            ActionType::CycleFilter if ui_type.is_filterable() => Some(String::from("Switch to the next filter")),
+            ActionType::CycleFilter => None,
+            ActionType::ToggleHelp => Some(String::from("Show or hide this help")),
+            ActionType::Back => Some(String::from("Go back or cancel a dialog")),
+            ActionType::Save => Some(String::from("Save the game")),
+            ActionType::DeclareWar if ui_type == UIType::Diplomacy => Some(String::from("Declare war on the selected neighbour")),
+            ActionType::DeclareWar => None,
+        }
+    }
 }
 
 /// Creates a crossterm terminal
@@ -45,7 +151,33 @@ pub fn create_crossterm_terminal() -> Rc<RefCell<Terminal<CrosstermBackend<Stdou
     Rc::new(RefCell::new(Terminal::new(backend).unwrap()))
 }
 
+/// UIError contains every error, which may occur in the user-interface. Recoverable errors are
+/// displayed in an error banner on top of the current view.
 #[derive(Hash,Debug)]
 pub enum UIError{
     MisconfiguredUIEvent(&'static str),
+    InvalidKeyBindings(String),
+    InvalidThemes(String),
+    Asset(AssetError),
+    Input(String),
+    Save(String),
+    Recording(String),
+    Command(String),
+}
+
+impl Display for UIError{
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+
+        match self{
+            UIError::MisconfiguredUIEvent(msg) => { write!(f, "Misconfigured ui-event: {msg}")}
+            UIError::InvalidKeyBindings(msg) => { write!(f, "Invalid key bindings: {msg}")}
+            UIError::InvalidThemes(msg) => { write!(f, "Invalid themes: {msg}")}
+            UIError::Asset(e) => { write!(f, "{e}")}
+            UIError::Input(msg) => { write!(f, "Unable to read input: {msg}")}
+            UIError::Save(msg) => { write!(f, "Unable to save the game: {msg}")}
+            UIError::Recording(msg) => { write!(f, "Unable to record the input: {msg}")}
+            UIError::Command(msg) => { write!(f, "Unable to apply the command: {msg}")}
+        }
+    }
 }
\ No newline at end of file
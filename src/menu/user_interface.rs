@@ -1,83 +1,136 @@
-use std::sync::{Arc,RwLock};
+use std::sync::Arc;
 use std::cell::{RefCell};
 use std::rc::Rc;
-use std::io::Stdout;
 use std::collections::HashMap;
-use std::ops::DerefMut;
+use std::time::Instant;
+
+use arc_swap::ArcSwap;
+use crossterm::event::{Event, MouseButton, MouseEvent, MouseEventKind};
 
 use tui::{
-    backend::CrosstermBackend,
-    style::{Color, Style},
-    widgets::{Block, Borders, List, ListItem},
+    backend::Backend,
     Terminal,
 };
 
-use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers, KeyEventState, KeyEventKind};
-
 use crate::menu::ui_foundations::*;
 use crate::menu::events::{UIEventBuilder,UIEvent};
-use crate::menu::assets;
+use crate::menu::key_bindings;
+use crate::menu::UIConfig;
+use crate::menu::view_registry::{ViewEntry, ViewRegistry};
+use crate::data::saves;
 use crate::game::GameState;
-use crate::menu::ui_foundations::ActionType::{ChangeView, HandleUITimeout, Scroll};
+use crate::game::commands::{Command, CommandSender};
+use crate::game::messages::Message;
+use crate::menu::ui_foundations::ActionType::{Back, ChangeView, Confirm, CycleFilter, DeclareWar, HandleUITimeout, Save, Scroll, ToggleHelp};
 use crate::menu::current_ui::CurrentUI;
+use crate::menu::rendering::dialog::{Confirmation, Dialog};
+use crate::menu::rendering::error_banner::ErrorBanner;
+use crate::menu::rendering::help_overlay::HelpOverlay;
+use crate::menu::rendering::rect_contains;
+use crate::menu::rendering::size_warning::SizeWarning;
+use crate::menu::themes::Themes;
+use crate::menu::rendering::toasts::Toasts;
+
+/// DialogPurpose denotes, what a modal dialog asks the player for and thus, how the selected
+/// option is handled
+#[derive(Debug, Copy, Clone)]
+enum DialogPurpose{
+    /// The player chooses how to react to the pending random event. This dialog can't be cancelled.
+    RandomEvent,
+    /// The player confirms quitting and decides whether to save before
+    Quit,
+    /// The player confirms overwriting the existing save file
+    OverwriteSave,
+    /// The player confirms declaring war on the neighbour with the given index
+    DeclareWar(usize),
+}
 
 /// Holds the current user-interface the common terminal as well as some general data structures, which
 /// map specified events to specified actions, e.g. a key input event 'v' will be mapped to an action
-/// to change the menu to the 'village' screen. It also holds the GameState in as a read-write-lock
-/// to ensure safe concurrent access.
-pub struct UserInterface{
+/// to change the menu to the 'village' screen. It reads the GameState from the immutable snapshot,
+/// which the game-loop publishes in an `Arc<ArcSwap<GameState>>` after every change, so that
+/// drawing never blocks the simulation. Changes of the game state are sent to the game-loop as
+/// commands instead.
+pub struct UserInterface<B: Backend>{
     /// The current user-interface, containing the UIType and a terminal-renderer
-    cur_ui: CurrentUI,
+    cur_ui: CurrentUI<B>,
     /// A reference to the common terminal, which will be used by every terminal-renderer of
     /// the current user-interface
-    terminal: Rc<RefCell<Terminal<CrosstermBackend<Stdout>>>>,
-    /// The general GameState, which is secured behind a Read-Write Lock.
-    game_state: Arc<RwLock<GameState>>,
+    terminal: Rc<RefCell<Terminal<B>>>,
+    /// The latest snapshot of the general GameState, which the game-loop publishes after every change
+    game_state: Arc<ArcSwap<GameState>>,
+    /// The sender of commands to the game-loop, which is the only one to change the GameState
+    commands: CommandSender,
     /// Some mapping from ui-events to actions
-    event_to_action_type: HashMap<UIEvent,ActionType>
+    event_to_action_type: HashMap<UIEvent,ActionType>,
+    /// The registry of every view, which can be reached from the menu
+    views: ViewRegistry,
+    /// A modal dialog together with its purpose, e.g. a random event, which awaits the player's
+    /// choice, or a confirmation. As long as it is open, it will receive every scroll-, confirm- and
+    /// back-action.
+    dialog: Option<(Dialog, DialogPurpose)>,
+    /// An overlay, which briefly displays new messages from the game
+    toasts: Toasts,
+    /// Whether the help, which lists every key binding, is displayed on top of the current view
+    show_help: bool,
+    /// A banner, which displays recoverable errors on top of everything else
+    error_banner: ErrorBanner,
+    /// The name of the save file in the saves-folder, to which the game is saved
+    save_file_name: String,
+    /// A warning, which is displayed instead of the current view, if the terminal is too small
+    size_warning: SizeWarning,
+    /// Every theme, which the player can choose from in the settings
+    themes: Themes,
+    /// The index of the active theme
+    theme: usize,
+    /// Whether the size warning has been displayed in the last rendering. In that case, the layout
+    /// of the current view is outdated.
+    is_too_small: bool,
+    /// The revision of the game state, which has been drawn last
+    drawn_revision: Option<u64>,
 }
 
-impl UserInterface {
+impl<B: Backend> UserInterface<B> {
 
-    /// Creates a new UserInterface, which makes use of the passed GameState. This method will create
-    /// a new crossterm-terminal and initialise the event-to-action map, which will be used by the
-    /// user-interface.
+    /// Creates a new UserInterface, which makes use of the passed GameState and draws into the passed
+    /// terminal. This method will initialise the event-to-action map from the key bindings, which
+    /// will be used by the user-interface.
     /// # Arguments
-    /// * `game_state` is the common game-state, which is accessible in the terminal.
-    pub fn new(game_state: Arc<RwLock<GameState>>) -> UserInterface{
+    /// * `terminal` is the terminal, into which every view is drawn, e.g. a crossterm-terminal
+    /// * `game_state` is the snapshot of the common game-state, which is accessible in the terminal.
+    /// * `commands` is the sender of commands, which change the game-state
+    /// * `config` contains the validated key bindings, from which the menu is generated as well, the
+    ///   validated themes, of which the default one is active at first, and the name of the save file.
+    pub fn new(terminal: Rc<RefCell<Terminal<B>>>, game_state: Arc<ArcSwap<GameState>>, commands: CommandSender, config: UIConfig) -> UserInterface<B>{
+
+        let UIConfig { key_bindings, themes, save_file_name, .. } = config;
+
         UserInterface{
-            terminal: create_crossterm_terminal(),
+            terminal,
             game_state,
-            cur_ui: CurrentUI::new(),
-            event_to_action_type: {
+            commands,
+            cur_ui: CurrentUI::new(themes.names()),
+            theme: themes.default_index(),
+            themes,
+            dialog: None,
+            save_file_name,
+            size_warning: SizeWarning::new(),
+            is_too_small: false,
+            drawn_revision: None,
+            toasts: Toasts::new(),
+            show_help: false,
+            error_banner: ErrorBanner::new(),
+            views: {
+
+                let mut views = ViewRegistry::new();
+
+                for (hotkey, ui_type) in key_bindings.view_hotkeys() {
+                    views.register(ViewEntry { title: ui_type.title(), hotkey: String::from(hotkey), ui_type });
+                }
 
-                let village_menu_event = UIEventBuilder::new().input_key_event(Event::Key(KeyEvent {
-                    code: KeyCode::Char('v'),
-                    modifiers: KeyModifiers::NONE,
-                    kind: KeyEventKind::Press,
-                    state: KeyEventState::NONE,
-                })).build().unwrap();
-
-                let craft_menu_event = UIEventBuilder::new().input_key_event(Event::Key(KeyEvent {
-                    code: KeyCode::Char('c'),
-                    modifiers: KeyModifiers::NONE,
-                    kind: KeyEventKind::Press,
-                    state: KeyEventState::NONE,
-                })).build().unwrap();
-
-                let diplomacy_menu_event = UIEventBuilder::new().input_key_event(Event::Key(KeyEvent {
-                    code: KeyCode::Char('d'),
-                    modifiers: KeyModifiers::NONE,
-                    kind: KeyEventKind::Press,
-                    state: KeyEventState::NONE,
-                })).build().unwrap();
-
-                let quit_event = UIEventBuilder::new().input_key_event(Event::Key(KeyEvent {
-                    code: KeyCode::Char('q'),
-                    modifiers: KeyModifiers::NONE,
-                    kind: KeyEventKind::Press,
-                    state: KeyEventState::NONE,
-                })).build().unwrap();
+                views
+            },
+            event_to_action_type: {
 
                 let timeout_start = UIEventBuilder::new()
                     .timeout_ui(UIType::Start)
@@ -89,29 +142,9 @@ impl UserInterface {
                     .build()
                     .unwrap();
 
-                let scroll_down = UIEventBuilder::new().input_key_event(Event::Key(KeyEvent {
-                    code: KeyCode::Down,
-                    modifiers: KeyModifiers::NONE,
-                    kind: KeyEventKind::Press,
-                    state: KeyEventState::NONE,
-                })).build().unwrap();
-
-                let scroll_up = UIEventBuilder::new().input_key_event(Event::Key(KeyEvent {
-                    code: KeyCode::Up,
-                    modifiers: KeyModifiers::NONE,
-                    kind: KeyEventKind::Press,
-                    state: KeyEventState::NONE,
-                })).build().unwrap();
-
-                let mut event_to_action_type  = HashMap::new();
-                event_to_action_type.insert(village_menu_event, ChangeView(UIType::Villages));
-                event_to_action_type.insert(craft_menu_event, ChangeView(UIType::Crafts));
-                event_to_action_type.insert(diplomacy_menu_event, ChangeView(UIType::Diplomacy));
-                event_to_action_type.insert(quit_event, ChangeView(UIType::Quit));
+                let mut event_to_action_type  = key_bindings.to_event_map();
                 event_to_action_type.insert(timeout_start, HandleUITimeout(UIType::Start));
                 event_to_action_type.insert(timeout_quit, HandleUITimeout(UIType::Quit));
-                event_to_action_type.insert(scroll_down, Scroll(ScrollingDirection::Down));
-                event_to_action_type.insert(scroll_up, Scroll(ScrollingDirection::Up));
 
                 event_to_action_type
             }
@@ -132,14 +165,58 @@ impl UserInterface {
         self.cur_ui.is_expired()
     }
 
+    /// Returns a help entry for every key binding, which has an effect in the current view. Every
+    /// entry consists of the key chord's label and a description of its action. The entries are
+    /// sorted by their descriptions.
+    fn get_help_entries(&self) -> Vec<(String, String)>{
+
+        let ui_type = self.get_current_uitype();
+
+        let mut entries: Vec<(String, String)> = self.event_to_action_type.iter()
+            .filter_map(|(event, action)| match (event.get_input_key_event(), action.description(ui_type)) {
+                (Some(Event::Key(key_event)), Some(description)) => Some((key_bindings::key_label(key_event), description)),
+                _ => None,
+            })
+            .collect();
+
+        entries.sort_by(|a, b| a.1.cmp(&b.1));
+        entries
+    }
+
+    /// Returns true, if the player is in the game, that is, neither the startup- nor the termination
+    /// screen is displayed.
+    fn is_in_game(&self) -> bool{
+        !matches!(self.get_current_uitype(), UIType::Start | UIType::Quit | UIType::Terminated)
+    }
+
     /// Returns the current UIType of the current user-interface.
     pub fn get_current_uitype(&self) -> UIType{
         self.cur_ui.ui_type
     }
 
-    /// Logically updates the terminal, if the need arises. For now it will only check, if the current
-    /// ui has expired and trigger a timeout-event in that case.
-    pub fn update_terminal(&mut self){
+    /// Displays a new message from the game as a toast
+    /// # Arguments
+    /// * `message` is the new message
+    pub fn add_notification(&mut self, message: Message){
+        self.toasts.push(message);
+    }
+
+    /// Displays a recoverable error in the error banner
+    /// # Arguments
+    /// * `error` is the error, which has occurred
+    pub fn report_error(&mut self, error: UIError){
+        log::error!(error:% = error; "Error reported to the player");
+        self.error_banner.push(error);
+    }
+
+    /// Logically updates the terminal, if the need arises. It will check, if the current ui has
+    /// expired and trigger a timeout-event in that case, and remove every expired toast and error.
+    /// Furthermore, it will open a dialog for any random event, which has occurred in the game and
+    /// awaits the player's choice. Returns true, if the terminal must be redrawn.
+    pub fn update_terminal(&mut self) -> bool{
+
+        let mut has_changed = self.toasts.remove_expired();
+        has_changed |= self.error_banner.remove_expired();
 
         if self.is_current_ui_type_expired() {
 
@@ -149,7 +226,49 @@ impl UserInterface {
                 .unwrap();
 
             self.process_ui_event(&timeout_event);
+            has_changed = true;
         }
+
+        if self.is_in_game() && self.dialog.is_none() {
+
+            let game_state = self.game_state.load_full();
+
+            if let Some(pending_event) = &game_state.pending_event {
+
+                let event_dialog = Dialog::new(
+                    pending_event.event.name.clone(),
+                    pending_event.description(&game_state.kingdom),
+                    pending_event.event.choices.iter().map(|c| c.label.clone()).collect(),
+                );
+
+                self.dialog = Some((event_dialog, DialogPurpose::RandomEvent));
+                has_changed = true;
+            }
+        }
+
+        has_changed
+    }
+
+    /// Returns the next time, at which the terminal changes by itself, e.g. because the current ui
+    /// or a toast expires. Returns None, if nothing will change without further events.
+    pub fn next_update_time(&self) -> Option<Instant>{
+
+        [
+            self.cur_ui.get_expiration_time(),
+            self.toasts.next_expiration_time(),
+            self.error_banner.next_expiration_time(),
+        ]
+            .into_iter()
+            .flatten()
+            .min()
+    }
+
+    /// Returns true, if the given revision of the game state has already been drawn, so that the
+    /// terminal must not be redrawn because of it
+    /// # Arguments
+    /// * `revision` is the revision of the game state, which has changed
+    pub fn has_drawn_revision(&self, revision: u64) -> bool{
+        self.drawn_revision.is_some_and(|drawn_revision| drawn_revision >= revision)
     }
 
     /// Renders/Redraws the terminal. The rendering will be handled by the RenderTerminal-trait, which
@@ -157,57 +276,521 @@ impl UserInterface {
     /// the craft-view, etc.
     pub fn render_terminal(&mut self){
 
-        let menu_items = assets::read_asset("menu.txt");
+        self.views.select(&self.cur_ui.get_path());
+
+        let is_in_game = self.is_in_game();
+        let help_overlay = (self.show_help && is_in_game)
+            .then(|| HelpOverlay::new(self.get_current_uitype(), self.get_help_entries()));
+        let game_state = self.game_state.load_full();
+        let cur_ui = &mut self.cur_ui;
+        let views = &self.views;
+        let dialog = &self.dialog;
+        let toasts = &self.toasts;
+        let error_banner = &self.error_banner;
+        let size_warning = &self.size_warning;
+        let theme = self.themes.get(self.theme);
+        let mut is_too_small = false;
+
+        self.drawn_revision = Some(game_state.get_revision());
+
+        self.terminal.borrow_mut().draw(|f| {
+
+            // Instead of garbling the views, only errors are displayed next to the warning
+            if size_warning.is_too_small(f.size()) {
+                is_too_small = true;
+                size_warning.render(f, theme);
+                error_banner.render(f, theme);
+                return;
+            }
+
+            cur_ui.render(f, views, &game_state, theme);
 
-        let menu_items : Vec<ListItem>= menu_items.lines().map(|s| ListItem::new(s)).collect();
+            if is_in_game {
+                toasts.render(f, theme);
+            }
+
+            if let Some(help_overlay) = &help_overlay {
+                help_overlay.render(f, theme);
+            }
+
+            if let Some((dialog, _)) = dialog {
+                dialog.render(f, theme);
+            }
+
+            error_banner.render(f, theme);
+        }).expect("Can render widget");
 
-        // A style with a brown background color
-        let menu_style : Style = Style::default().bg(Color::Rgb(139, 69, 19));
+        self.is_too_small = is_too_small;
+    }
 
-        let menu_widget = List::new(menu_items)
-            .style(menu_style)
-            .block(Block::default().borders(Borders::ALL).title("Menu"));
+    /// Adapts the terminal to its new size. It isn't redrawn here, as the event counts as a change
+    /// of the view, which is drawn right afterwards anyway.
+    fn resize_terminal(&mut self){
 
-        self.cur_ui.render(self.terminal.borrow_mut().deref_mut(),&menu_widget, Arc::clone(&self.game_state));
+        self.terminal.borrow_mut()
+            .autoresize()
+            .expect("Can resize terminal");
     }
 
     /// Processes ui-events, by determining the corresponding action for that event, which is either
     /// a ChangeView to change the view, e.g. from a village-view to a crafts-view, or handling of a
     /// timeout-event. As the latter only occurs for the initial startup-screen and the final termination
     /// screen, only those two specific cases must be addressed.
+    /// While a dialog is open, any action apart from scrolling through its choices, confirming a
+    /// choice, cancelling the dialog and timeouts will be ignored. In views with a text input, typed
+    /// characters are passed to the text input instead of being mapped to actions. Mouse events are
+    /// hit-tested against the current layout. A resize of the terminal only adapts the terminal's size.
     /// # Arguments
-    /// * `event` is the UIEvent, which must be handled. It is either a timeout-event or a key-input event.
+    /// * `event` is the UIEvent, which must be handled. It is either a timeout-event or an input event.
     pub fn process_ui_event(&mut self, event: &UIEvent) {
 
-        if let Some(action_for_event) = self.get_action_for_event(&event){
+        if let Some(Event::Resize(_, _)) = event.get_input_key_event() {
+            self.resize_terminal();
+            return;
+        }
+
+        if let (None, Some(Event::Key(key_event))) = (&self.dialog, event.get_input_key_event()) {
+            if let Some(text_input) = self.cur_ui.text_input_mut() {
+                if text_input.handle_key_event(key_event) {
+                    return;
+                }
+            }
+        }
+
+        if let Some(Event::Mouse(mouse_event)) = event.get_input_key_event() {
+            self.process_mouse_event(mouse_event);
+            return;
+        }
+
+        if let Some(action_for_event) = self.get_action_for_event(event).copied(){
+            self.process_action(action_for_event);
+        }
+    }
+
+    /// Executes an action, which has been triggered by a key, a mouse event or a timeout. While a
+    /// dialog is open, the action is passed to the dialog instead.
+    /// # Arguments
+    /// * `action_for_event` is the action, which must be executed
+    fn process_action(&mut self, action_for_event: ActionType) {
+
+        if let Some((dialog, purpose)) = &mut self.dialog {
 
             match action_for_event{
+                Scroll(ScrollingDirection::Up) => dialog.select_previous(),
+                Scroll(ScrollingDirection::Down) => dialog.select_next(),
+                Confirm => {
+                    let choice = dialog.get_selected();
+                    let purpose = *purpose;
+                    self.dialog = None;
+                    self.resolve_dialog(purpose, choice);
+                }
+                Back if !matches!(purpose, DialogPurpose::RandomEvent) => {
+                    self.dialog = None;
+                }
+                _ => {}
+            }
+
+            if !matches!(action_for_event, HandleUITimeout(_)) {
+                return;
+            }
+        }
+
+        match action_for_event{
+
+            Scroll(scroll_direction) => {
+                self.cur_ui.scroll(scroll_direction);
+            }
+            Confirm => {
+
+                match self.get_current_uitype() {
+                    UIType::Villages if !self.game_state.load().kingdom.villages.is_empty() => {
+                        self.cur_ui.push_ui_type(UIType::VillageDetail);
+                    }
+                    UIType::VillageDetail => {
+                        self.cur_ui.push_ui_type(UIType::RenameVillage);
+                    }
+                    UIType::RenameVillage => {
+                        self.rename_village();
+                    }
+                    UIType::Settings => {
+                        self.theme = self.cur_ui.get_selection();
+                    }
+                    _ => {}
+                }
+            }
+            CycleFilter => {
+                self.cur_ui.cycle_filter();
+            }
+            ToggleHelp => {
+                self.show_help = !self.show_help;
+            }
+            Back => {
+                self.cur_ui.go_back();
+            }
+            Save => {
+
+                if saves::save_file_exists(&self.save_file_name) {
+                    self.open_confirmation(
+                        "Save",
+                        format!("Overwrite the save file '{}'?", self.save_file_name),
+                        DialogPurpose::OverwriteSave,
+                    );
+                } else {
+                    self.save_game();
+                }
+            }
+            DeclareWar => {
+
+                if self.get_current_uitype() == UIType::Diplomacy {
 
-                Scroll(scroll_direction) => {
-                    self.cur_ui.scroll(*scroll_direction);
+                    let neighbour = self.cur_ui.get_selection();
+                    let name = self.game_state.load().kingdom.neighbours.get(neighbour)
+                        .map(|n| n.name.clone());
+
+                    if let Some(name) = name {
+                        self.open_confirmation(
+                            "Declare war",
+                            format!("Do you really want to declare war on {name}?"),
+                            DialogPurpose::DeclareWar(neighbour),
+                        );
+                    }
                 }
-                ChangeView(ui_type) => {
+            }
+            ChangeView(UIType::Quit) if self.is_in_game() => {
+                self.open_confirmation("Quit", String::from("Save before quitting?"), DialogPurpose::Quit);
+            }
+            ChangeView(ui_type) => {
+                self.cur_ui.change_ui_type(ui_type);
+            }
+            HandleUITimeout(ui_type) => {
 
-                    if self.get_current_uitype() == *ui_type {
+                match ui_type {
+                    UIType::Start => {
                         self.cur_ui.change_ui_type(UIType::Main)
-                    } else{
-                        self.cur_ui.change_ui_type(*ui_type);
-                    }
+                    },
+                    UIType::Quit => {
+                        self.cur_ui.change_ui_type(UIType::Terminated)
+                    },
+                    _ => {}
                 }
-                HandleUITimeout(ui_type) => {
-
-                    match ui_type {
-                        UIType::Start => {
-                            self.cur_ui.change_ui_type(UIType::Main)
-                        },
-                        UIType::Quit => {
-                            self.cur_ui.change_ui_type(UIType::Terminated)
-                        },
-                        _ => {}
+            }
+        }
+    }
+
+    /// Processes mouse events. The wheel scrolls like the arrow keys. A left click on an entry of the
+    /// menu changes the view and a left click into a list view selects the entry under the cursor.
+    /// The clicked entries are determined from the layout, in which the current view has been drawn.
+    /// # Arguments
+    /// * `mouse_event` is the mouse event, which must be handled
+    fn process_mouse_event(&mut self, mouse_event: &MouseEvent) {
+
+        let (column, row) = (mouse_event.column, mouse_event.row);
+
+        match mouse_event.kind {
+            MouseEventKind::ScrollUp => self.process_action(Scroll(ScrollingDirection::Up)),
+            MouseEventKind::ScrollDown => self.process_action(Scroll(ScrollingDirection::Down)),
+            MouseEventKind::Down(MouseButton::Left) if self.dialog.is_none() && self.is_in_game() && !self.is_too_small => {
+
+                let layout = self.cur_ui.get_layout();
+
+                if rect_contains(layout.menu, column, row) {
+                    if let Some(ui_type) = self.views.get_view_at(layout.menu, row) {
+                        self.process_action(ChangeView(ui_type));
                     }
+                } else if rect_contains(layout.content, column, row) {
+                    self.cur_ui.select_at(row);
                 }
             }
+            _ => {}
         }
+    }
+
+    /// Opens a confirmation dialog, which offers the answers Yes, No and Cancel
+    /// # Arguments
+    /// * `title` is the title of the dialog
+    /// * `message` is the question, which the player is asked
+    /// * `purpose` determines, how the player's answer is handled
+    fn open_confirmation(&mut self, title: &str, message: String, purpose: DialogPurpose){
+        self.dialog = Some((Dialog::confirmation(String::from(title), message), purpose));
+    }
+
+    /// Handles the option, which the player has selected in a dialog
+    /// # Arguments
+    /// * `purpose` is the purpose of the dialog
+    /// * `choice` is the index of the selected option
+    fn resolve_dialog(&mut self, purpose: DialogPurpose, choice: usize){
+
+        let confirmation = Confirmation::from_index(choice);
+
+        match (purpose, confirmation) {
+            (DialogPurpose::RandomEvent, _) => {
+                self.commands.execute(Command::ResolveEvent(choice));
+            }
+            (DialogPurpose::Quit, Confirmation::Yes | Confirmation::No) => {
+
+                // If saving fails, the player stays in the game to react to the error
+                let may_quit = confirmation == Confirmation::No || self.save_game();
+
+                if may_quit {
+                    self.cur_ui.change_ui_type(UIType::Quit);
+                }
+            }
+            (DialogPurpose::OverwriteSave, Confirmation::Yes) => {
+                self.save_game();
+            }
+            (DialogPurpose::DeclareWar(neighbour), Confirmation::Yes) => {
+                self.commands.execute(Command::DeclareWar(neighbour));
+            }
+            _ => {}
+        }
+    }
+
+    /// Renames the village of the rename view to the typed name and returns to the previous view.
+    /// A blank name is ignored.
+    fn rename_village(&mut self){
+
+        let village = self.cur_ui.get_selection();
+        let name = self.cur_ui.text_input_mut()
+            .map(|text_input| text_input.get_text().trim().to_string())
+            .unwrap_or_default();
+
+        if !name.is_empty() {
+            self.commands.execute(Command::RenameVillage(village, name));
+            self.cur_ui.go_back();
+        }
+    }
+
+    /// Writes the game state to the save file. Returns true, if the game has been saved, and reports
+    /// the error otherwise.
+    fn save_game(&mut self) -> bool{
+
+        let result = saves::write_save_file(&self.save_file_name, &self.game_state.load());
+
+        match result {
+            Ok(()) => true,
+            Err(e) => {
+                self.report_error(UIError::Save(e));
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::fs;
+    use std::path::Path;
+    use crossterm::event::KeyModifiers;
+    use tui::backend::TestBackend;
+
+    use super::*;
+    use crate::data::saves::{load_save_file, save_file_exists};
+    use crate::menu::key_bindings::KeyBindings;
+
+    /// Returns a user-interface, which displays the main view in a terminal of a TestBackend. The
+    /// commands are dropped, as there is no game-loop.
+    /// # Arguments
+    /// * `save_file_name` is the name of the save file, to which the game is saved
+    fn user_interface(save_file_name: &str) -> UserInterface<TestBackend> {
+
+        let terminal = Rc::new(RefCell::new(Terminal::new(TestBackend::new(100, 30)).unwrap()));
+        let game_state = Arc::new(ArcSwap::from_pointee(load_save_file("k_best_kingdom.json").unwrap()));
+        let commands = CommandSender::new(flume::unbounded().0);
+        let config = UIConfig {
+            key_bindings: KeyBindings::load().unwrap(),
+            themes: Themes::load().unwrap(),
+            save_file_name: String::from(save_file_name),
+            max_fps: 30,
+            recorder: None,
+            replay: None,
+        };
+
+        let mut ui = UserInterface::new(terminal, game_state, commands, config);
+        ui.cur_ui.change_ui_type(UIType::Main);
+        ui
+    }
+
+    /// Opens the quit dialog and confirms the given answer
+    /// # Arguments
+    /// * `ui` is the user-interface, in which the player quits
+    /// * `confirmation` is the answer of the player
+    fn answer_quit_dialog(ui: &mut UserInterface<TestBackend>, confirmation: Confirmation) {
+
+        ui.process_action(ChangeView(UIType::Quit));
+        assert!(matches!(ui.dialog, Some((_, DialogPurpose::Quit))));
+
+        for _ in 0..confirmation as usize {
+            ui.process_action(Scroll(ScrollingDirection::Down));
+        }
+
+        ui.process_action(Confirm);
+        assert!(ui.dialog.is_none());
+    }
+
+    /// Returns the descriptions of the help entries of the current view
+    /// # Arguments
+    /// * `ui` is the user-interface, whose help is displayed
+    fn help_descriptions(ui: &UserInterface<TestBackend>) -> Vec<String> {
+        ui.get_help_entries().into_iter().map(|(_, description)| description).collect()
+    }
+
+    /// Returns the row of the terminal, which has been drawn last
+    /// # Arguments
+    /// * `ui` is the user-interface, whose terminal is read
+    /// * `y` is the index of the row
+    fn row(ui: &UserInterface<TestBackend>, y: u16) -> String {
+
+        let terminal = ui.terminal.borrow();
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.width).map(|x| buffer.get(x, y).symbol.as_str()).collect()
+    }
+
+    #[test]
+    fn quitting_with_yes_saves_the_game() {
+
+        let save_file_name = format!("railan-test-quit-{}.json", std::process::id());
+        let mut ui = user_interface(&save_file_name);
+
+        answer_quit_dialog(&mut ui, Confirmation::Yes);
+
+        let saved = save_file_exists(&save_file_name);
+        if saved {
+            fs::remove_file(Path::new(saves::SAVES_PATH).join(&save_file_name)).unwrap();
+        }
+
+        assert!(saved);
+        assert_eq!(ui.get_current_uitype(), UIType::Quit);
+    }
+
+    #[test]
+    fn quitting_with_no_doesnt_save_the_game() {
+
+        let save_file_name = format!("railan-test-no-save-{}.json", std::process::id());
+        let mut ui = user_interface(&save_file_name);
+
+        answer_quit_dialog(&mut ui, Confirmation::No);
+
+        assert!(!save_file_exists(&save_file_name));
+        assert_eq!(ui.get_current_uitype(), UIType::Quit);
+    }
+
+    #[test]
+    fn cancelling_the_quit_dialog_stays_in_the_game() {
+
+        let mut ui = user_interface("unused.json");
+
+        answer_quit_dialog(&mut ui, Confirmation::Cancel);
+        assert_eq!(ui.get_current_uitype(), UIType::Main);
+
+        ui.process_action(ChangeView(UIType::Quit));
+        ui.process_action(Back);
+        assert!(ui.dialog.is_none());
+        assert_eq!(ui.get_current_uitype(), UIType::Main);
+    }
+
+    #[test]
+    fn a_failed_save_keeps_the_player_in_the_game() {
+
+        let mut ui = user_interface("missing-directory/save.json");
+
+        answer_quit_dialog(&mut ui, Confirmation::Yes);
+
+        assert_eq!(ui.get_current_uitype(), UIType::Main);
+        assert!(ui.error_banner.next_expiration_time().is_some());
+    }
+
+    #[test]
+    fn clicks_are_ignored_while_the_terminal_is_too_small() {
+
+        let mut ui = user_interface("unused.json");
+        let click = |row| MouseEvent { kind: MouseEventKind::Down(MouseButton::Left), column: 2, row, modifiers: KeyModifiers::NONE };
+
+        ui.terminal.borrow_mut().backend_mut().resize(60, 20);
+        ui.render_terminal();
+        assert!(ui.is_too_small);
+
+        // The first entry of the menu would be hit in a terminal, which is large enough
+        ui.process_mouse_event(&click(1));
+        assert_eq!(ui.get_current_uitype(), UIType::Main);
+
+        ui.terminal.borrow_mut().backend_mut().resize(100, 30);
+        ui.render_terminal();
+        assert!(!ui.is_too_small);
+
+        ui.process_mouse_event(&click(1));
+        assert_ne!(ui.get_current_uitype(), UIType::Main);
+    }
+
+    #[test]
+    fn the_help_lists_the_key_bindings_of_the_current_view() {
+
+        let mut ui = user_interface("unused.json");
+
+        let main_help = help_descriptions(&ui);
+        assert!(main_help.contains(&String::from("Show or hide this help")));
+        assert!(main_help.contains(&String::from("Open Villages")));
+        assert!(!main_help.contains(&String::from("Switch to the next filter")));
+        assert!(!main_help.contains(&String::from("Declare war on the selected neighbour")));
+
+        // The entries are sorted by their descriptions
+        let mut sorted = main_help.clone();
+        sorted.sort();
+        assert_eq!(main_help, sorted);
+
+        ui.process_action(ChangeView(UIType::MessageLog));
+        assert!(help_descriptions(&ui).contains(&String::from("Switch to the next filter")));
+
+        ui.process_action(ChangeView(UIType::Diplomacy));
+        assert!(help_descriptions(&ui).contains(&String::from("Declare war on the selected neighbour")));
+    }
+
+    #[test]
+    fn the_help_is_toggled_on_top_of_the_current_view() {
+
+        let mut ui = user_interface("unused.json");
+
+        ui.process_action(ToggleHelp);
+        ui.render_terminal();
+        assert!(ui.show_help);
+        assert!(row(&ui, 4).contains("Help - Main"));
+
+        ui.process_action(ToggleHelp);
+        ui.render_terminal();
+        assert!(!ui.show_help);
+        assert!(!row(&ui, 4).contains("Help - Main"));
+    }
+
+    #[test]
+    fn reported_errors_are_displayed_on_top_of_everything_else() {
+
+        let mut ui = user_interface("unused.json");
+        ui.process_action(ToggleHelp);
+
+        ui.report_error(UIError::Command(String::from("There is no village 9")));
+        ui.render_terminal();
+
+        assert!(row(&ui, 0).starts_with("┌Error"));
+        assert!(row(&ui, 1).contains("Unable to apply the command: There is no village 9"));
+        assert!(ui.next_update_time().is_some());
+    }
+
+    #[test]
+    fn only_revisions_after_the_drawn_one_must_be_drawn() {
+
+        let mut ui = user_interface("unused.json");
+        let revision = ui.game_state.load().get_revision();
+        assert!(!ui.has_drawn_revision(revision));
+
+        ui.render_terminal();
+        assert!(ui.has_drawn_revision(revision));
+
+        let mut changed = GameState::clone(&ui.game_state.load());
+        changed.mark_changed();
+        ui.game_state.store(Arc::new(changed));
+        assert!(!ui.has_drawn_revision(revision + 1));
 
+        ui.render_terminal();
+        assert!(ui.has_drawn_revision(revision + 1));
     }
 }
@@ -0,0 +1,219 @@
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    widgets::{Block, Borders, List, ListItem, ListState},
+    Frame,
+};
+
+use crate::menu::rendering::list_index_at;
+use crate::menu::themes::Theme;
+use crate::menu::ui_foundations::UIType;
+
+/// A view, which can be reached from the menu
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ViewEntry{
+    /// The title of the view
+    pub title: &'static str,
+    /// The label of the key chord, which opens the view
+    pub hotkey: String,
+    /// The type of the view
+    pub ui_type: UIType,
+}
+
+/// A registry of every view, which can be reached from the menu. It provides the menu, which is
+/// displayed next to every view, and highlights the current view within it. The menu-widget is only
+/// rebuilt, if the set of registered views changes.
+pub struct ViewRegistry{
+    /// The registered views in the order of the menu
+    entries: Vec<ViewEntry>,
+    /// The menu-widget, which lists every registered view
+    menu_widget: List<'static>,
+    /// The index of the current view within the menu, if it is listed there
+    selected: Option<usize>,
+    /// The path of views, which leads to the current view, e.g. "Main > Villages > Village"
+    breadcrumbs: String,
+}
+
+impl ViewRegistry{
+
+    /// Creates a registry without any views
+    pub fn new() -> ViewRegistry{
+        ViewRegistry{
+            entries: vec![],
+            menu_widget: build_menu_widget(&[]),
+            selected: None,
+            breadcrumbs: String::new(),
+        }
+    }
+
+    /// Registers a view at the end of the menu. A view, which has already been registered, is
+    /// ignored.
+    /// # Arguments
+    /// * `entry` is the view, which should be registered
+    pub fn register(&mut self, entry: ViewEntry){
+
+        if self.entries.contains(&entry){
+            return;
+        }
+
+        self.entries.push(entry);
+        self.menu_widget = build_menu_widget(&self.entries);
+    }
+
+    /// Highlights the current view in the menu and updates the breadcrumbs. If the current view is
+    /// not registered, e.g. because it is nested, its closest registered ancestor is highlighted.
+    /// # Arguments
+    /// * `path` is the path of views, which leads from the root to the current view
+    pub fn select(&mut self, path: &[UIType]){
+
+        self.selected = path.iter()
+            .rev()
+            .find_map(|ui_type| self.entries.iter().position(|e| e.ui_type == *ui_type));
+
+        self.breadcrumbs = path.iter()
+            .map(|ui_type| ui_type.title())
+            .collect::<Vec<&str>>()
+            .join(" > ");
+    }
+
+    /// Returns the path of views, which leads to the current view. It is displayed in the title bar
+    /// of every view.
+    pub fn breadcrumbs(&self) -> &str{
+        &self.breadcrumbs
+    }
+
+    /// Returns the width, which the menu needs to display its widest entry including its borders
+    pub fn get_min_width(&self) -> u16{
+        self.entries.iter()
+            .map(|e| menu_label(e).chars().count() as u16 + 2)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the view, whose entry the menu displays in the given row of the terminal. If the row
+    /// shows no entry, None is returned.
+    /// # Arguments
+    /// * `area` is the area of the frame, which the menu covers
+    /// * `row` is the row of the terminal
+    pub fn get_view_at(&self, area: Rect, row: u16) -> Option<UIType>{
+        list_index_at(area, row, self.selected.unwrap_or(0), self.entries.len())
+            .map(|index| self.entries[index].ui_type)
+    }
+
+    /// Draws the menu into the given area of the frame
+    /// # Arguments
+    /// * `frame` is the terminal's frame in which the menu shall be drawn
+    /// * `area` is the area of the frame, which the menu covers
+    /// * `theme` is the theme, whose colors are used
+    pub fn render<B: Backend>(&self, frame: &mut Frame<B>, area: Rect, theme: &Theme){
+
+        let mut state = ListState::default();
+        state.select(self.selected);
+
+        let menu_widget = self.menu_widget.clone()
+            .style(theme.menu_style())
+            .highlight_style(theme.menu_highlight_style());
+
+        frame.render_stateful_widget(menu_widget, area, &mut state);
+    }
+}
+
+/// Builds the menu-widget, which lists the given views. Its colors are applied, when it is rendered.
+/// # Arguments
+/// * `entries` are the views, which are listed
+fn build_menu_widget(entries: &[ViewEntry]) -> List<'static>{
+
+    let menu_items : Vec<ListItem> = entries.iter()
+        .map(|e| ListItem::new(menu_label(e)))
+        .collect();
+
+    List::new(menu_items)
+        .block(Block::default().borders(Borders::ALL).title("Menu"))
+}
+
+/// Returns the label of a view in the menu, e.g. "[v] Villages"
+/// # Arguments
+/// * `entry` is the view, which is labelled
+fn menu_label(entry: &ViewEntry) -> String{
+    format!("[{}] {}", entry.hotkey, entry.title)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use tui::{backend::TestBackend, Terminal};
+
+    use super::*;
+
+    /// Returns the entry of a view, which is opened by the given hotkey
+    /// # Arguments
+    /// * `hotkey` is the label of the hotkey
+    /// * `ui_type` is the type of the view
+    fn entry(hotkey: &str, ui_type: UIType) -> ViewEntry {
+        ViewEntry { title: ui_type.title(), hotkey: String::from(hotkey), ui_type }
+    }
+
+    /// Returns a registry of the villages, crafts and quit views
+    fn registry() -> ViewRegistry {
+
+        let mut views = ViewRegistry::new();
+        views.register(entry("v", UIType::Villages));
+        views.register(entry("c", UIType::Crafts));
+        views.register(entry("Ctrl+q", UIType::Quit));
+        views
+    }
+
+    /// Draws the menu of the registry and returns the drawn rows
+    /// # Arguments
+    /// * `views` is the registry, whose menu is drawn
+    fn draw(views: &ViewRegistry) -> Vec<String> {
+
+        let mut terminal = Terminal::new(TestBackend::new(15, 5)).unwrap();
+        terminal.draw(|f| views.render(f, f.size(), &Theme::default())).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        (0..5).map(|y| (0..15).map(|x| buffer.get(x, y).symbol.as_str()).collect()).collect()
+    }
+
+    #[test]
+    fn views_are_listed_once_in_the_order_of_their_registration() {
+
+        let mut views = registry();
+        views.register(entry("v", UIType::Villages));
+
+        assert_eq!(draw(&views), vec![
+            "┌Menu─────────┐",
+            "│[v] Villages │",
+            "│[c] Crafts   │",
+            "│[Ctrl+q] Quit│",
+            "└─────────────┘",
+        ]);
+        assert_eq!(views.get_min_width(), 15);
+        assert_eq!(ViewRegistry::new().get_min_width(), 0);
+    }
+
+    #[test]
+    fn the_closest_registered_view_of_the_path_is_highlighted() {
+
+        let mut views = registry();
+        let area = Rect::new(0, 0, 15, 5);
+
+        views.select(&[UIType::Main, UIType::Villages, UIType::VillageDetail]);
+        assert_eq!(views.selected, Some(0));
+        assert_eq!(views.breadcrumbs(), "Main > Villages > Village");
+
+        views.select(&[UIType::Main, UIType::Crafts]);
+        assert_eq!(views.selected, Some(1));
+        assert_eq!(views.breadcrumbs(), "Main > Crafts");
+
+        views.select(&[UIType::Main]);
+        assert_eq!(views.selected, None);
+        assert_eq!(views.breadcrumbs(), "Main");
+
+        // Clicks hit the entries of the menu, but neither its border nor the rows below
+        assert_eq!(views.get_view_at(area, 0), None);
+        assert_eq!(views.get_view_at(area, 1), Some(UIType::Villages));
+        assert_eq!(views.get_view_at(area, 3), Some(UIType::Quit));
+        assert_eq!(views.get_view_at(area, 4), None);
+    }
+}
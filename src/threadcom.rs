@@ -1,13 +1,23 @@
-use crossbeam::channel::{bounded,Sender,Receiver};
+use flume::{bounded,Sender,Receiver};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
 use std::thread::JoinHandle;
-use crossterm::event::Event;
+use crate::game::commands::CommandRequest;
+use crate::game::game_loop::Notification;
+use crate::menu::UIError;
 
 /// The ThreadCommunication struct holds some multi-sender-multi-receiver channels.
 pub struct ThreadCommunication {
     /// A channel to communicate a "quit"-string, if a user wants to quit the program
     terminate_app_channel: (Sender<String>, Receiver<String>),
-    /// A channel to communicate input-key events from a listener to executor
-    input_key_event_channel: (Sender<Event>, Receiver<Event>),
+    /// A channel to communicate commands, which change the game, from the menu-renderer to the game-loop
+    command_channel: (Sender<CommandRequest>, Receiver<CommandRequest>),
+    /// A channel to communicate changes of the game from the game-loop to the menu-renderer
+    notification_channel: (Sender<Notification>, Receiver<Notification>),
+    /// A channel to communicate recoverable errors from any thread to the menu-renderer
+    error_channel: (Sender<UIError>, Receiver<UIError>),
+    /// A flag, which pauses the game-loop, while the menu-renderer drives the game, e.g. in a replay
+    simulation_paused: Arc<AtomicBool>,
     /// A vector with handles over all spawned threads
     handles: Vec<JoinHandle<()>>,
 }
@@ -15,20 +25,29 @@ pub struct ThreadCommunication {
 impl ThreadCommunication {
 
     /// Returns a ThreadCommunication containing all necessary communication-channels.
-    /// As the implementation is based upon three threads, one for the game-loop, one
-    /// for the event-listener and one for the menu-renderer, the channels are set up
-    /// as follows:
-    /// * `terminate_app_channel` - will be bounded by 2 messages so the menu thread can
-    /// inform the other two threads about termination by sending a "quit"-token.
-    /// * `input_key_event_channel` - will be bounded by 1. It is used to send
-    /// key input events from the event-listener thread to the menu-thread
+    /// As the implementation is based upon two threads, one for the game-loop and one for the
+    /// menu-renderer, which also listens for input events, the channels are set up as follows:
+    /// * `terminate_app_channel` - will be bounded by 1 message so the menu thread can
+    ///   inform the game-loop about termination by sending a "quit"-token.
+    /// * `command_channel` - will be bounded by 1. It is used to send commands from the menu-thread
+    ///   to the game-loop thread, which is the only one to change the game state
+    /// * `notification_channel` - will be bounded by 100. It is used to send messages and
+    ///   changes of the game state from the game-loop thread to the menu-thread, which is woken
+    ///   up by them
+    /// * `error_channel` - will be bounded by 16. Any thread may use it to report recoverable
+    ///   errors to the menu-thread, which displays them in an error banner
     pub fn new() -> ThreadCommunication {
 
-        let quit_x = bounded(2);
-        let event_x = bounded(1);
+        let quit_x = bounded(1);
+        let command_x = bounded(1);
+        let notification_x = bounded(100);
+        let error_x = bounded(16);
         ThreadCommunication {
             terminate_app_channel: quit_x,
-            input_key_event_channel: event_x,
+            command_channel: command_x,
+            notification_channel: notification_x,
+            error_channel: error_x,
+            simulation_paused: Arc::new(AtomicBool::new(false)),
             handles: vec![],
         }
     }
@@ -41,12 +60,28 @@ impl ThreadCommunication {
         Sender::clone(&self.terminate_app_channel.0)
     }
 
-    pub fn get_event_tx(&self) -> Sender<Event>{
-        Sender::clone(&self.input_key_event_channel.0)
+    pub fn get_command_tx(&self) -> Sender<CommandRequest>{
+        Sender::clone(&self.command_channel.0)
     }
 
-    pub fn get_event_rx(&self) -> Receiver<Event>{
-        Receiver::clone(&self.input_key_event_channel.1)
+    pub fn get_command_rx(&self) -> Receiver<CommandRequest>{
+        Receiver::clone(&self.command_channel.1)
+    }
+
+    pub fn get_notification_tx(&self) -> Sender<Notification>{
+        Sender::clone(&self.notification_channel.0)
+    }
+
+    pub fn get_notification_rx(&self) -> Receiver<Notification>{
+        Receiver::clone(&self.notification_channel.1)
+    }
+
+    pub fn get_error_tx(&self) -> Sender<UIError>{
+        Sender::clone(&self.error_channel.0)
+    }
+
+    pub fn get_error_rx(&self) -> Receiver<UIError>{
+        Receiver::clone(&self.error_channel.1)
     }
 
     /// Adds another handle to the handles-vector
@@ -56,6 +91,11 @@ impl ThreadCommunication {
         self.handles.push(handle);
     }
 
+    /// Returns the flag, which pauses the game-loop, while it is set
+    pub fn get_simulation_paused(&self) -> Arc<AtomicBool>{
+        Arc::clone(&self.simulation_paused)
+    }
+
     /// Waits until all threads in the handles-vector have terminated
     pub fn wait_on_shutdown(self) {
 